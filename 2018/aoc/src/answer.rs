@@ -0,0 +1,68 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Answer to a puzzle part; most are numbers, some are text like a list of
+/// steps or coordinates.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+  Int(i64),
+  Text(String),
+}
+
+impl Display for Answer {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Answer::Int(n) => write!(f, "{}", n),
+      Answer::Text(s) => write!(f, "{}", s),
+    }
+  }
+}
+
+impl Answer {
+  /** JSON number or string */
+  pub fn to_json(&self) -> String {
+    match self {
+      Answer::Int(n) => n.to_string(),
+      Answer::Text(s) => {
+        let mut json = String::with_capacity(s.len() + 2);
+        json.push('"');
+        for c in s.chars() {
+          match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json += &format!("\\u{:04x}", c as u32),
+            c => json.push(c),
+          }
+        }
+        json.push('"');
+        json
+      }
+    }
+  }
+}
+
+// integers beyond `i64` become text rather than wrapping round
+macro_rules! from_int {
+  ($($t:ty),*) => {
+    $(impl From<$t> for Answer {
+      fn from(n: $t) -> Self {
+        i64::try_from(n)
+          .map_or_else(|_| Answer::Text(n.to_string()), Answer::Int)
+      }
+    })*
+  };
+}
+
+from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl From<String> for Answer {
+  fn from(s: String) -> Self {
+    Answer::Text(s)
+  }
+}
+
+impl From<&str> for Answer {
+  fn from(s: &str) -> Self {
+    Answer::Text(s.to_string())
+  }
+}
@@ -0,0 +1,140 @@
+use crate::Point;
+use std::{
+  error::Error,
+  fmt::{self, Display, Formatter},
+  ops::{Index, IndexMut},
+};
+
+/// Rectangular grid of cells stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+  cells: Vec<T>,
+  width: usize,
+  height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+  pub fn new(width: usize, height: usize, fill: T) -> Self {
+    Grid {
+      cells: vec![fill; width * height],
+      width,
+      height,
+    }
+  }
+}
+
+impl Grid<u8> {
+  /** Grid of the bytes of `input`'s lines; all lines should be as wide */
+  pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+    let mut cells = Vec::with_capacity(input.len());
+    let mut width = None;
+    let mut height = 0;
+    for (idx, line) in input.lines().enumerate() {
+      match width {
+        None => width = Some(line.len()),
+        Some(w) if w != line.len() => {
+          return Err(
+            format!(
+              "Expected {} columns, found {} at line {}",
+              w,
+              line.len(),
+              idx + 1
+            )
+            .into(),
+          )
+        }
+        Some(_) => (),
+      }
+      cells.extend_from_slice(line.as_bytes());
+      height += 1;
+    }
+    Ok(Grid {
+      cells,
+      width: width.unwrap_or(0),
+      height,
+    })
+  }
+}
+
+impl<T> Grid<T> {
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn cells(&self) -> &[T] {
+    &self.cells
+  }
+
+  pub fn cells_mut(&mut self) -> &mut [T] {
+    &mut self.cells
+  }
+
+  pub fn contains(&self, p: Point) -> bool {
+    (0..self.width as i32).contains(&p.0)
+      && (0..self.height as i32).contains(&p.1)
+  }
+
+  /** Index into `cells()` of `p` if it lies within the grid */
+  pub fn index_of(&self, p: Point) -> Option<usize> {
+    self
+      .contains(p)
+      .then(|| p.1 as usize * self.width + p.0 as usize)
+  }
+
+  pub fn point_of(&self, idx: usize) -> Point {
+    Point((idx % self.width) as i32, (idx / self.width) as i32)
+  }
+
+  pub fn get(&self, p: Point) -> Option<&T> {
+    self.index_of(p).map(|idx| &self.cells[idx])
+  }
+
+  pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+    self.index_of(p).map(move |idx| &mut self.cells[idx])
+  }
+
+  /** Cells of row `y` */
+  pub fn row(&self, y: usize) -> &[T] {
+    &self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  /** Points of all cells in reading order */
+  pub fn points(&self) -> impl Iterator<Item = Point> {
+    let width = self.width as i32;
+    (0..self.height as i32)
+      .flat_map(move |y| (0..width).map(move |x| Point(x, y)))
+  }
+}
+
+impl<T> Index<Point> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, p: Point) -> &T {
+    self
+      .get(p)
+      .unwrap_or_else(|| panic!("{} is outside the grid", p))
+  }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+  fn index_mut(&mut self, p: Point) -> &mut T {
+    let idx = self
+      .index_of(p)
+      .unwrap_or_else(|| panic!("{} is outside the grid", p));
+    &mut self.cells[idx]
+  }
+}
+
+/// Draws a byte grid as text, one line per row.
+impl Display for Grid<u8> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    for y in 0..self.height {
+      writeln!(f, "{}", String::from_utf8_lossy(self.row(y)))?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,36 @@
+use std::{
+  error::Error,
+  fmt::Display,
+  io::{self, BufRead, Read},
+  str::FromStr,
+};
+
+/** All of standard input */
+pub fn read_stdin() -> io::Result<String> {
+  let mut input = String::new();
+  io::stdin().read_to_string(&mut input)?;
+  Ok(input)
+}
+
+/** Lines of standard input without their terminators */
+pub fn stdin_lines() -> io::Result<Vec<String>> {
+  io::stdin().lock().lines().collect()
+}
+
+/** Each non-blank line of `input` parsed as a `T`; errors name the line */
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, Box<dyn Error>>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  input
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(idx, line)| {
+      line
+        .parse()
+        .map_err(|e| format!("{} at line {}", e, idx + 1).into())
+    })
+    .collect()
+}
@@ -0,0 +1,35 @@
+//! Pieces shared by the 2018 solvers: points, grids, typed answers and their
+//! output, input reading and debug-only printing.
+
+mod answer;
+mod grid;
+mod input;
+mod output;
+mod point;
+
+pub use answer::Answer;
+pub use grid::Grid;
+pub use input::{parse_lines, read_stdin, stdin_lines};
+pub use output::{operands, Format, Output};
+pub use point::Point;
+
+/// `eprint!` in debug builds; compiles to nothing in release builds.  Goes
+/// to stderr to keep answers on stdout clean.
+#[macro_export]
+macro_rules! dbg_print {
+  ($($args:tt)*) => {
+    if cfg!(debug_assertions) {
+      eprint!($($args)*);
+    }
+  };
+}
+
+/// `eprintln!` in debug builds; compiles to nothing in release builds.
+#[macro_export]
+macro_rules! debug_print {
+  ($($args:tt)*) => {
+    if cfg!(debug_assertions) {
+      eprintln!($($args)*);
+    }
+  };
+}
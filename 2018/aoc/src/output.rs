@@ -0,0 +1,104 @@
+use crate::Answer;
+use std::{env, error::Error, str::FromStr, time::Instant};
+
+/// How solvers print answers: prose lines or JSON records.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+  #[default]
+  Text,
+  /// One `{year, day, part, answer, elapsed_ms}` object per line
+  Json,
+}
+
+impl FromStr for Format {
+  type Err = Box<dyn Error>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "text" => Ok(Format::Text),
+      "json" => Ok(Format::Json),
+      _ => {
+        Err(format!("Unrecognized format: {}; expected text or json", s).into())
+      }
+    }
+  }
+}
+
+/** Command-line arguments but for the program and `--format` with its value */
+pub fn operands() -> Vec<String> {
+  let mut operands = Vec::new();
+  let mut args = env::args().skip(1);
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--format" => {
+        args.next();
+      }
+      _ => operands.push(arg),
+    }
+  }
+  operands
+}
+
+/// Prints answers of a day’s parts in the chosen format; anything else a
+/// solver prints should go to stderr so JSON output stays parseable.
+pub struct Output {
+  year: u16,
+  day: u8,
+  format: Format,
+}
+
+impl Output {
+  pub fn new(year: u16, day: u8, format: Format) -> Self {
+    Output { year, day, format }
+  }
+
+  /** Output in the format given with `--format text|json`; text by default */
+  pub fn from_args(year: u16, day: u8) -> Result<Self, Box<dyn Error>> {
+    let format = match env::args().skip_while(|arg| arg != "--format").nth(1) {
+      Some(format) => format.parse()?,
+      None => Format::default(),
+    };
+    Ok(Output::new(year, day, format))
+  }
+
+  /** Solves `part` with `solve`, printing the answer as `label: answer` or as
+   * a JSON record with the time taken */
+  pub fn part(&self, part: u8, label: &str, solve: impl FnOnce() -> Answer) {
+    let start = Instant::now();
+    let answer = solve();
+    self.print(part, label, &answer, start);
+  }
+
+  /** `part` for solvers that can fail e.g. on a trap; errors print nothing */
+  pub fn try_part<E>(
+    &self,
+    part: u8,
+    label: &str,
+    solve: impl FnOnce() -> Result<Answer, E>,
+  ) -> Result<(), E> {
+    let start = Instant::now();
+    let answer = solve()?;
+    self.print(part, label, &answer, start);
+    Ok(())
+  }
+
+  fn print(&self, part: u8, label: &str, answer: &Answer, start: Instant) {
+    let elapsed = start.elapsed();
+    match self.format {
+      // multi-line answers e.g. day 10’s message start on a line of their own
+      Format::Text => match answer.to_string().contains('\n') {
+        true => println!("{}:\n{}", label, answer),
+        false => println!("{}: {}", label, answer),
+      },
+      Format::Json => println!(
+        "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\
+         \"elapsed_ms\":{:.3}}}",
+        self.year,
+        self.day,
+        part,
+        answer.to_json(),
+        elapsed.as_secs_f64() * 1e3
+      ),
+    }
+  }
+}
@@ -0,0 +1,93 @@
+use std::{
+  cmp::Ordering,
+  error::Error,
+  fmt::{self, Display, Formatter},
+  ops::{Add, AddAssign, Sub, SubAssign},
+  str::FromStr,
+};
+
+/// Point on a 2D grid; X grows rightwards and Y downwards.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Point(pub i32, pub i32);
+
+impl Point {
+  pub const UP: Point = Point(0, -1);
+  pub const DOWN: Point = Point(0, 1);
+  pub const LEFT: Point = Point(-1, 0);
+  pub const RIGHT: Point = Point(1, 0);
+
+  pub fn manhattan(self, other: Point) -> i32 {
+    (self.0 - other.0).abs() + (self.1 - other.1).abs()
+  }
+
+  /** Points sharing an edge with this one, in reading order */
+  pub fn neighbours(self) -> [Point; 4] {
+    [
+      self + Point::UP,
+      self + Point::LEFT,
+      self + Point::RIGHT,
+      self + Point::DOWN,
+    ]
+  }
+}
+
+// Reading order i.e. by Y then X; deriving would order by X first as it
+// compares fields in declaration order
+impl Ord for Point {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (self.1, self.0).cmp(&(other.1, other.0))
+  }
+}
+
+impl PartialOrd for Point {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Add for Point {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    Point(self.0 + other.0, self.1 + other.1)
+  }
+}
+
+impl Sub for Point {
+  type Output = Self;
+
+  fn sub(self, other: Self) -> Self {
+    Point(self.0 - other.0, self.1 - other.1)
+  }
+}
+
+impl AddAssign for Point {
+  fn add_assign(&mut self, other: Self) {
+    *self = *self + other;
+  }
+}
+
+impl SubAssign for Point {
+  fn sub_assign(&mut self, other: Self) {
+    *self = *self - other;
+  }
+}
+
+/// Formats as `X,Y`, the way puzzle answers are given.
+impl Display for Point {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.0, self.1)
+  }
+}
+
+/// Parses `X,Y` with optional spaces around either coordinate.
+impl FromStr for Point {
+  type Err = Box<dyn Error>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (x, y) = s
+      .split_once(',')
+      .ok_or_else(|| format!("Expected `X,Y` point, found `{}`", s))?;
+    Ok(Point(x.trim().parse()?, y.trim().parse()?))
+  }
+}
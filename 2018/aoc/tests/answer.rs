@@ -0,0 +1,19 @@
+use aoc::Answer;
+
+#[test]
+fn integers_in_range() {
+  assert_eq!(Answer::from(i64::MAX as u64), Answer::Int(i64::MAX));
+  assert_eq!(Answer::from(i64::MIN), Answer::Int(i64::MIN));
+  assert_eq!(Answer::from(usize::MIN), Answer::Int(0));
+}
+
+#[test]
+fn integers_beyond_i64() {
+  // these used to wrap round, `u64::MAX` showing as -1
+  let answer = Answer::from(u64::MAX);
+  assert_eq!(answer.to_string(), "18446744073709551615");
+  assert_eq!(
+    Answer::from(i64::MAX as u64 + 1).to_string(),
+    "9223372036854775808"
+  );
+}
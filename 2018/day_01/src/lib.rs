@@ -0,0 +1,31 @@
+// https://adventofcode.com/2018/day/1
+
+use aoc::Answer;
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Frequency changes
+pub type Input = Vec<i32>;
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  aoc::parse_lines(input)
+}
+
+/** Frequency after all changes */
+pub fn part1(freq: &Input) -> Answer {
+  freq.iter().sum::<i32>().into()
+}
+
+/** First frequency reached twice, cycling through the changes */
+pub fn part2(freq: &Input) -> Answer {
+  let mut resulting_freqs = HashSet::new();
+  resulting_freqs.insert(0); // 0 is the frequency device starts with
+  let mut interrim_freq = 0;
+  for f in freq.iter().cycle() {
+    interrim_freq += f;
+    if !resulting_freqs.insert(interrim_freq) {
+      break;
+    }
+  }
+  interrim_freq.into()
+}
@@ -1,30 +1,10 @@
-// https://adventofcode.com/2018/day/1
-
-use std::collections::HashSet;
-use std::io::{self, BufRead, Error};
-use std::result::Result;
-
-fn main() {
-  let freq: Vec<i32> = io::stdin()
-    .lock()
-    .lines()
-    .map(|line: Result<String, Error>| {
-      line.unwrap_or_default().parse().unwrap_or_default()
-    })
-    .collect();
-
-  // part 1
-  println!("{}", freq.iter().sum::<i32>());
-
-  // part 2
-  let mut resulting_freqs = HashSet::new();
-  resulting_freqs.insert(0); // 0 is the frequency device starts with
-  let mut interrim_freq = 0;
-  for f in freq.iter().cycle() {
-    interrim_freq += f;
-    if !resulting_freqs.insert(interrim_freq) {
-      break;
-    }
-  }
-  println!("First repeating frequency: {}", interrim_freq);
+use aoc::Output;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+  let input = day_01::parse(&aoc::read_stdin()?)?;
+  let out = Output::from_args(2018, 1)?;
+  out.part(1, "Resulting frequency", || day_01::part1(&input));
+  out.part(2, "First repeating frequency", || day_01::part2(&input));
+  Ok(())
 }
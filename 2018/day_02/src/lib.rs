@@ -0,0 +1,84 @@
+use aoc::Answer;
+use std::error::Error;
+
+/// Box IDs
+pub type Input = Vec<String>;
+
+/** IDs of lowercase letters, all as long */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let box_ids = input
+    .lines()
+    .filter(|l| !l.is_empty())
+    .map(str::to_string)
+    .collect::<Vec<_>>();
+  for (idx, id) in box_ids.iter().enumerate() {
+    if !id.bytes().all(|b| b.is_ascii_lowercase()) {
+      return Err(
+        format!("Invalid box ID `{}` at line {}", id, idx + 1).into(),
+      );
+    }
+    if id.len() != box_ids[0].len() {
+      return Err(format!("Box ID `{}` differs in length", id).into());
+    }
+  }
+  Ok(box_ids)
+}
+
+/** Count of IDs with a letter twice times count of those with one thrice */
+pub fn part1(box_ids: &Input) -> Answer {
+  let count: [u16; 2] = box_ids
+    .iter()
+    // map string to array of flags for double and triple char repetitions
+    .map(|s| -> [bool; 2] {
+      let mut occur = [0u8; 26];
+      s.chars() // compute per-char occurance count
+        .for_each(|c| occur[c as usize - 'a' as usize] += 1);
+      occur
+        .iter() // fold all occurances to a single flag array
+        .fold([false; 2], |f, &n| [f[0] | (n == 2), f[1] | (n == 3)])
+    })
+    // accumulate repetition count of all strings
+    .fold([0; 2], |acc, s| {
+      [acc[0] + s[0] as u16, acc[1] + s[1] as u16]
+    });
+  (count[0] as u32 * count[1] as u32).into()
+}
+
+/// Letters common to the two IDs differing by one letter.
+///
+/// Panics if no two IDs differ by at most one letter.
+pub fn part2(box_ids: &Input) -> Answer {
+  // Test all string pairs (nC₂) for Levenshtein distance ≤ 1.
+  // An element needn’t be compared with preceding ones as it’d’ve
+  // already been done; hence the skip().
+  box_ids
+    .iter()
+    .enumerate()
+    .find_map(|(idx, s1)| {
+      box_ids
+        .iter()
+        .skip(idx + 1) // skip to elements after x
+        .find_map(|s2| fuzzy_intersection(s1, s2))
+    })
+    .expect("No two box IDs differ by a letter")
+    .into()
+}
+
+/// Returns intersection of equal length string slices if their
+/// Levenshtein distance is ≤ 1.
+fn fuzzy_intersection(s1: &str, s2: &str) -> Option<String> {
+  assert_eq!(s1.len(), s2.len()); // doesn’t work for unequal strings
+  let mut intersection = String::with_capacity(s1.len());
+  let mut mismatches = 0;
+  for (c1, c2) in s1.chars().zip(s2.chars()) {
+    if c1 != c2 {
+      mismatches += 1;
+      if mismatches > 1 {
+        return None;
+      }
+    } else {
+      intersection.push(c1);
+    }
+  }
+  Some(intersection)
+}
@@ -0,0 +1,138 @@
+use aoc::{Answer, Point};
+use std::error::Error;
+use std::str::FromStr;
+
+//
+//       +------------------------------+     +----------+
+//       |                 +---------+  |     |          |
+//       |                 |         |  |     +----------+
+//   +---+-----------------+---------+--+-----------+
+//   |   |                 |         |  |           |
+//   |   |                 |         |  |           |               +--------+
+//   |   |                 |         |  |      +----+---------------+-+      |
+//   |   |                 |         |  |      |    |               | |      |
+//   +---+-----------------+---------+--+------+----+               | |      |
+//       |                 |         |  |      |                    +-+------+
+//       +-----------------+---------+--+      |        +---------+   |
+//                         |         |         |        |         |   |
+//                         |         |         |        |         |   |
+//                         +---------+         |        |         |   |
+//                                             |        +---------+   |
+//                                             |                      |
+//                                             +----------------------+
+//
+//
+
+#[derive(Debug)]
+struct Rect {
+  id: i32,
+  left_top: Point,
+  right_bot: Point, // right-bottom is exclusive; for simple width/height calcs
+}
+
+impl Rect {
+  fn new(id: i32, left_top: Point, wd_ht: Point) -> Self {
+    Rect {
+      id,
+      left_top,
+      right_bot: left_top + wd_ht,
+    }
+  }
+
+  fn width(&self) -> i32 {
+    self.right_bot.0 - self.left_top.0
+  }
+
+  fn height(&self) -> i32 {
+    self.right_bot.1 - self.left_top.1
+  }
+}
+
+impl FromStr for Rect {
+  type Err = Box<dyn Error>;
+
+  /** Parses a claim like `#1 @ 167,777: 23x12` */
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid claim `{}`", s);
+    let (id, rest) = s
+      .strip_prefix('#')
+      .and_then(|s| s.split_once('@'))
+      .ok_or_else(invalid)?;
+    let (left_top, size) = rest.split_once(':').ok_or_else(invalid)?;
+    let (width, height) = size.trim().split_once('x').ok_or_else(invalid)?;
+    Ok(Rect::new(
+      id.trim().parse()?,
+      left_top.parse()?,
+      Point(width.parse()?, height.parse()?),
+    ))
+  }
+}
+
+const MAX_WIDTH: usize = 1000;
+const MAX_HEIGHT: usize = 1000;
+
+fn draw(canvas: &mut [u8], r: &Rect) {
+  let offset = r.left_top.1 * MAX_WIDTH as i32 + r.left_top.0;
+  for i in 0..r.height() {
+    for j in 0..r.width() {
+      let o = (j + offset + i * MAX_WIDTH as i32) as usize;
+      canvas[o] += 1;
+    }
+  }
+}
+
+fn count_intersections(canvas: &[u8]) -> u32 {
+  canvas.iter().fold(0, |acc, x| acc + ((x > &1) as u32))
+}
+
+fn in_tact(canvas: &[u8], r: &Rect) -> bool {
+  // checking the borders to be 1 is good enough
+  let left = r.left_top.0 as usize;
+  let top = r.left_top.1 as usize;
+  let width = r.width() as usize;
+
+  (0..r.height()).all(|y| {
+    let offset = (MAX_WIDTH * (top + y as usize)) + left;
+    canvas[offset..(offset + width)].iter().all(|&c| c == 1u8)
+  })
+}
+
+/// Claims and the fabric with each square inch's count of claims.
+pub struct Input {
+  rects: Vec<Rect>,
+  canvas: Vec<u8>,
+}
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let rects: Vec<Rect> = aoc::parse_lines(input)?;
+  let mut canvas = vec![0u8; MAX_WIDTH * MAX_HEIGHT];
+  for r in &rects {
+    if r.left_top.0 < 0
+      || r.left_top.1 < 0
+      || r.right_bot.0 > MAX_WIDTH as i32
+      || r.right_bot.1 > MAX_HEIGHT as i32
+    {
+      return Err(format!("Claim #{} exceeds the fabric", r.id).into());
+    }
+    draw(&mut canvas, r);
+  }
+  Ok(Input { rects, canvas })
+}
+
+/** Square inches within two or more claims */
+pub fn part1(input: &Input) -> Answer {
+  count_intersections(&input.canvas).into()
+}
+
+/// ID of the only claim not overlapping others.
+///
+/// Panics if every claim overlaps another.
+pub fn part2(input: &Input) -> Answer {
+  input
+    .rects
+    .iter()
+    .find(|r| in_tact(&input.canvas, r))
+    .expect("No unique claims!")
+    .id
+    .into()
+}
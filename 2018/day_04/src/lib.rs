@@ -0,0 +1,123 @@
+use aoc::Answer;
+use std::collections::HashMap;
+use std::error::Error;
+
+type GuardId = u16;
+
+#[derive(Default)]
+struct GuardSchedule {
+  asleep_dur: u16,               // in minutes
+  sleep_interval: Vec<(u8, u8)>, // [a, b) in minutes
+}
+
+/// Sleep schedule of each guard.
+pub struct Input {
+  guard_schedule: HashMap<GuardId, GuardSchedule>,
+}
+
+fn error(line: usize, msg: impl AsRef<str>) -> Box<dyn Error> {
+  format!("{} at line {}", msg.as_ref(), line).into()
+}
+
+/** Records like `[1518-11-01 00:05] falls asleep` in any order */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let mut records = input
+    .lines()
+    .enumerate()
+    .filter(|(_, l)| !l.is_empty())
+    .map(|(idx, l)| {
+      let (stamp, event) = l
+        .strip_prefix('[')
+        .and_then(|l| l.split_once("] "))
+        .ok_or_else(|| error(idx + 1, "Expected `[TIMESTAMP] EVENT`"))?;
+      let minute = stamp
+        .rsplit_once(':')
+        .and_then(|(_, m)| m.parse::<u8>().ok())
+        .filter(|&m| m < 60)
+        .ok_or_else(|| error(idx + 1, format!("Invalid time `{}`", stamp)))?;
+      Ok((stamp, minute, event, idx + 1))
+    })
+    .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+  // timestamps are zero-padded so they sort chronologically as text
+  records.sort_unstable_by_key(|&(stamp, ..)| stamp);
+
+  // digest guard schedules
+  let mut guard_schedule: HashMap<GuardId, GuardSchedule> = HashMap::new();
+  let mut id = None;
+  let mut slept_at = None;
+  for (_, minute, event, line) in records {
+    match event
+      .split_ascii_whitespace()
+      .collect::<Vec<_>>()
+      .as_slice()
+    {
+      ["Guard", guard, "begins", "shift"] => {
+        id = Some(
+          guard
+            .strip_prefix('#')
+            .and_then(|g| g.parse().ok())
+            .ok_or_else(|| error(line, format!("Invalid guard `{}`", guard)))?,
+        );
+        slept_at = None;
+      }
+      ["falls", "asleep"] => slept_at = Some(minute),
+      ["wakes", "up"] => {
+        let (id, slept_at) = id
+          .zip(slept_at.take())
+          .filter(|&(_, slept_at)| slept_at <= minute)
+          .ok_or_else(|| error(line, "Waking up without falling asleep"))?;
+        let shed = guard_schedule.entry(id).or_default();
+        shed.asleep_dur += (minute - slept_at) as u16;
+        shed.sleep_interval.push((slept_at, minute));
+      }
+      _ => return Err(error(line, format!("Unknown event `{}`", event))),
+    }
+  }
+  if guard_schedule.is_empty() {
+    return Err("No guard fell asleep".into());
+  }
+  Ok(Input { guard_schedule })
+}
+
+// mintue and its occurance frequency pair
+struct MinFreq(u8, u8);
+
+/// Returns the most slept minute with frequency
+fn most_slept_min(shed: &GuardSchedule) -> Option<MinFreq> {
+  let mut occurances = [0u8; 60];
+  for i in &shed.sleep_interval {
+    (i.0..i.1).for_each(|i| occurances[i as usize] += 1);
+  }
+  // https://stackoverflow.com/a/58103194/183120
+  let max_idx = occurances
+    .iter()
+    .enumerate()
+    .max_by_key(|(_idx, &val)| val) // find max by val
+    .map(|(idx, _val)| idx); // but obtain idx as result
+  if let Some(idx) = max_idx {
+    return Some(MinFreq(idx as u8, occurances[idx]));
+  }
+  None
+}
+
+/** Strategy 1: the guard asleep the longest times their sleepiest minute */
+pub fn part1(input: &Input) -> Answer {
+  let (&id, shed) = input
+    .guard_schedule
+    .iter()
+    .max_by_key(|(_, shed)| shed.asleep_dur)
+    .unwrap();
+  let MinFreq(min, _) = most_slept_min(shed).unwrap();
+  (id as u32 * min as u32).into()
+}
+
+/** Strategy 2: the guard asleep most often on a minute times the minute */
+pub fn part2(input: &Input) -> Answer {
+  let (id, MinFreq(min, _)) = input
+    .guard_schedule
+    .iter()
+    .filter_map(|(&id, shed)| Some((id, most_slept_min(shed)?)))
+    .max_by_key(|(_, MinFreq(_, freq))| *freq)
+    .unwrap();
+  (id as u32 * min as u32).into()
+}
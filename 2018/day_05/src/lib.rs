@@ -0,0 +1,60 @@
+use aoc::Answer;
+use std::error::Error;
+
+const FIRST: u8 = b'A';
+const LAST: u8 = b'Z';
+const CASE_DIFF: u8 = 32;
+
+/// Polymer units; input is pure ASCII so use bytes, not a String.
+pub struct Input {
+  polymer: Vec<u8>,
+}
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let polymer = input.trim_end().as_bytes().to_vec();
+  match polymer.iter().find(|c| !c.is_ascii_alphabetic()) {
+    Some(&c) => Err(format!("Invalid unit `{}`", c as char).into()),
+    None => Ok(Input { polymer }),
+  }
+}
+
+fn fold(cur: u8, polymer: &mut Vec<u8>, filter: &[u8]) {
+  if !filter.contains(&cur) {
+    let prev = match polymer.last() {
+      Some(last) => *last,
+      None => 0, // NUL
+    };
+    if (prev != cur)  // avoid ‘aa’ from passing
+      && ((char::from(prev) == char::from(cur).to_ascii_lowercase())
+      ||  (char::from(prev) == char::from(cur).to_ascii_uppercase()))
+    {
+      polymer.pop();
+    } else {
+      polymer.push(cur);
+    }
+  }
+}
+
+/** Length of `polymer` fully reacted, skipping units in `filter` */
+fn reduce(polymer: &[u8], filter: &[u8]) -> usize {
+  // reduced.len() ≤ polymer.len(); preallocate to avoid reallocation and copy
+  let mut reduced = Vec::with_capacity(polymer.len());
+  for &cur in polymer {
+    fold(cur, &mut reduced, filter);
+  }
+  reduced.len()
+}
+
+/** Units left after the polymer reacts fully */
+pub fn part1(input: &Input) -> Answer {
+  reduce(&input.polymer, &[]).into()
+}
+
+/** Length of the shortest polymer with one unit type removed */
+pub fn part2(input: &Input) -> Answer {
+  (FIRST..=LAST)
+    .map(|letter| reduce(&input.polymer, &[letter, letter + CASE_DIFF]))
+    .min()
+    .unwrap()
+    .into()
+}
@@ -0,0 +1,118 @@
+use aoc::{Answer, Point};
+use std::error::Error;
+
+type LocId = usize;
+type Dist = i32;
+type LocDist = (LocId, Dist);
+
+#[derive(Copy, Clone, Debug)]
+struct Location {
+  id: LocId,
+  pt: Point,
+}
+
+/// Locations and the rectangle bounding them.
+pub struct Map {
+  locs: Vec<Location>,
+  left: Dist,
+  top: Dist,
+  right: Dist,
+  bottom: Dist,
+}
+
+impl Map {
+  // X → and Y ↓
+
+  fn new(locs: Vec<Location>) -> Self {
+    let left = locs
+      .iter()
+      .min_by_key(|&loc| loc.pt.0)
+      .map(|&loc| loc.pt.0)
+      .unwrap();
+    let right = locs
+      .iter()
+      .max_by_key(|&loc| loc.pt.0)
+      .map(|&loc| loc.pt.0)
+      .unwrap();
+    let top = locs
+      .iter()
+      .min_by_key(|&loc| loc.pt.1)
+      .map(|&loc| loc.pt.1)
+      .unwrap();
+    let bottom = locs
+      .iter()
+      .max_by_key(|&loc| loc.pt.1)
+      .map(|&loc| loc.pt.1)
+      .unwrap();
+    Map {
+      locs,
+      left,
+      top,
+      right,
+      bottom,
+    }
+  }
+
+  fn is_on_edge(&self, pt: Point) -> bool {
+    (pt.0 == self.left)
+      || (pt.0 == self.right)
+      || (pt.1 == self.top)
+      || (pt.1 == self.bottom)
+  }
+}
+
+pub type Input = Map;
+
+const DIST_SUM_THRESHOLD: Dist = 10_000;
+
+/** Coordinates like `118, 274`, one per line */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let locs: Vec<Location> = aoc::parse_lines::<Point>(input)?
+    .into_iter()
+    .enumerate()
+    .map(|(id, pt)| Location { id, pt })
+    .collect();
+  if locs.len() < 2 {
+    return Err("Expected at least two coordinates".into());
+  }
+  Ok(Map::new(locs))
+}
+
+fn points(map: &Map) -> impl Iterator<Item = Point> + '_ {
+  (map.top..=map.bottom)
+    .flat_map(move |row| (map.left..=map.right).map(move |col| Point(col, row)))
+}
+
+/** Size of the largest finite area closest to a single location */
+pub fn part1(map: &Map) -> Answer {
+  // area of each location; `None` if infinite i.e. it reaches the edge
+  let mut areas = vec![Some(0u32); map.locs.len()];
+  for pt in points(map) {
+    let mut candidates: Vec<LocDist> = map
+      .locs
+      .iter()
+      .map(|l| (l.id, l.pt.manhattan(pt)))
+      .collect();
+    candidates.sort_unstable_by_key(|&(_id, dist)| dist);
+    // if not a tie between two locations
+    if candidates[0].1 != candidates[1].1 {
+      let area = &mut areas[candidates[0].0];
+      *area = match map.is_on_edge(pt) {
+        true => None,
+        false => area.map(|a| a + 1),
+      };
+    }
+  }
+  areas.into_iter().flatten().max().unwrap_or(0).into()
+}
+
+/** Size of the region with total distance to all locations under 10,000 */
+pub fn part2(map: &Map) -> Answer {
+  points(map)
+    .filter(|&pt| {
+      let dist_sum: Dist = map.locs.iter().map(|l| l.pt.manhattan(pt)).sum();
+      dist_sum < DIST_SUM_THRESHOLD
+    })
+    .count()
+    .into()
+}
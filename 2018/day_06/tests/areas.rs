@@ -0,0 +1,15 @@
+use aoc::Answer;
+
+#[test]
+fn sample_largest_finite_area() {
+  let map = day_06::parse(include_str!("../input/test")).unwrap();
+  assert_eq!(day_06::part1(&map), Answer::from(17u32));
+}
+
+#[test]
+fn infinite_areas_are_skipped() {
+  // the outer locations own far more squares, all reaching the edge
+  let map = day_06::parse("0, 0\n0, 20\n20, 0\n20, 20\n10, 10").unwrap();
+  let area = day_06::part1(&map);
+  assert_eq!(area, Answer::from(181u32));
+}
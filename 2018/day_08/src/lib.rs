@@ -0,0 +1,139 @@
+use aoc::Answer;
+use std::error::Error;
+
+#[derive(Debug)]
+struct NodeHeader {
+  node_id: u16,
+  metadata_count: u16,
+  child_count: u16,
+}
+
+struct Node {
+  metadata: Vec<u16>,
+  children: Vec<u16>,
+}
+
+impl Node {
+  fn new(data_capacity: usize, child_capacity: usize) -> Self {
+    Node {
+      metadata: Vec::<u16>::with_capacity(data_capacity),
+      children: Vec::<u16>::with_capacity(child_capacity),
+    }
+  }
+}
+
+fn read_number<'a, It>(it: &mut It) -> Result<u16, Box<dyn Error>>
+where
+  It: Iterator<Item = &'a str>,
+{
+  let number = it.next().ok_or("Tree ends abruptly")?;
+  Ok(number.parse()?)
+}
+
+fn read_header<'a, It>(
+  mut it: &mut It,
+  node_id: u16,
+) -> Result<NodeHeader, Box<dyn Error>>
+where
+  It: Iterator<Item = &'a str>,
+{
+  let child_count = read_number(&mut it)?;
+  let metadata_count = read_number(&mut it)?;
+  Ok(NodeHeader {
+    node_id,
+    child_count,
+    metadata_count,
+  })
+}
+
+fn node_value(tree: &[Node], idx: u16) -> u32 {
+  let mut stack = Vec::<u16>::with_capacity(tree.len());
+  let mut value: u32 = 0;
+  stack.push(idx);
+  while let Some(n) = stack.pop() {
+    let node = &tree[n as usize];
+    // NOTE: memoization opportunity; since child indices can repeat in
+    // metadata, store node value once computed to avoid recalculation
+    if node.children.is_empty() {
+      value += node.metadata.iter().sum::<u16>() as u32;
+    } else {
+      stack.extend(
+        node
+          .metadata
+          .iter()
+          .filter_map(|i| i.checked_sub(1))
+          .filter(|&i| i < node.children.len() as u16)
+          .map(|i| node.children[i as usize]),
+      );
+    }
+  }
+  value
+}
+
+/// Nodes of the tree in the order they appear; the first is the root.
+pub struct Input {
+  nodes: Vec<Node>,
+}
+
+/** Tree as whitespace-separated numbers */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let mut iter = input.split_ascii_whitespace().peekable();
+
+  let mut stack = Vec::<NodeHeader>::with_capacity(256);
+  let mut nodes = Vec::<Node>::with_capacity(256);
+  while iter.peek().is_some() {
+    let is_header = match stack.last() {
+      Some(node_header) => node_header.child_count > 0,
+      None if nodes.is_empty() => true,
+      None => return Err("Numbers past the root node".into()),
+    };
+    // print!("Header: {}\t", is_header);
+    if is_header {
+      let id = nodes.len() as u16;
+      if let Some(parent) = stack.last_mut() {
+        nodes[parent.node_id as usize].children.push(id);
+      }
+      let header = read_header(&mut iter, id)?;
+      if header.metadata_count == 0 {
+        return Err(format!("Node {} has no metadata", id).into());
+      }
+      // println!("{:?}\t", header);
+      nodes.push(Node::new(
+        header.metadata_count as usize,
+        header.child_count as usize,
+      ));
+      stack.push(header);
+    } else {
+      let metadata = read_number(&mut iter)?;
+      // use unwrap as we’re sure the stack has an element
+      let node_header = stack.last_mut().unwrap();
+      nodes[node_header.node_id as usize].metadata.push(metadata);
+      // println!("  metadata: {} for {}\t", metadata, node_header.node_id);
+      node_header.metadata_count -= 1;
+      if node_header.metadata_count == 0 {
+        stack.pop();
+        if let Some(parent) = stack.last_mut() {
+          parent.child_count -= 1;
+        }
+      }
+    }
+  }
+  if !stack.is_empty() || nodes.is_empty() {
+    return Err("Tree ends abruptly".into());
+  }
+  Ok(Input { nodes })
+}
+
+/** Sum of all metadata entries */
+pub fn part1(input: &Input) -> Answer {
+  input
+    .nodes
+    .iter()
+    .fold(0u32, |acc, n| acc + n.metadata.iter().sum::<u16>() as u32)
+    .into()
+}
+
+/** Value of the root node */
+pub fn part2(input: &Input) -> Answer {
+  node_value(&input.nodes, 0).into()
+}
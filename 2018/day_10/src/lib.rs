@@ -0,0 +1,87 @@
+use aoc::{Answer, Point};
+use std::error::Error;
+use std::str::FromStr;
+
+/// Position and velocity of a star.
+#[derive(Debug, Copy, Clone)]
+pub struct Light(pub Point, pub Point);
+
+impl FromStr for Light {
+  type Err = Box<dyn Error>;
+
+  /** Parses `position=< 9,  1> velocity=< 0,  2>` */
+  fn from_str(line: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid light `{}`", line);
+    let mut vectors = line.split(['<', '>']).skip(1).step_by(2);
+    let mut next = || -> Result<Point, Self::Err> {
+      vectors.next().ok_or_else(invalid)?.parse()
+    };
+    Ok(Light(next()?, next()?))
+  }
+}
+
+pub type Input = Vec<Light>;
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let lights: Vec<Light> = aoc::parse_lines(input)?;
+  match lights.is_empty() {
+    true => Err("No lights in the sky".into()),
+    false => Ok(lights),
+  }
+}
+
+fn at(lights: &[Light], time: i32) -> impl Iterator<Item = Point> + '_ {
+  lights
+    .iter()
+    .map(move |l| l.0 + Point(l.1 .0 * time, l.1 .1 * time))
+}
+
+/** Top-left and bottom-right corners of the stars at `time` */
+fn bounds(lights: &[Light], time: i32) -> (Point, Point) {
+  at(lights, time).fold(
+    (Point(i32::MAX, i32::MAX), Point(i32::MIN, i32::MIN)),
+    |(min, max), p| {
+      (
+        Point(min.0.min(p.0), min.1.min(p.1)),
+        Point(max.0.max(p.0), max.1.max(p.1)),
+      )
+    },
+  )
+}
+
+fn area(lights: &[Light], time: i32) -> i64 {
+  let (min, max) = bounds(lights, time);
+  (max.0 - min.0) as i64 * (max.1 - min.1) as i64
+}
+
+/** Seconds till the stars come closest i.e. spell out the message */
+fn message_time(lights: &[Light]) -> i32 {
+  let mut time = 0;
+  while area(lights, time + 1) < area(lights, time) {
+    time += 1;
+  }
+  time
+}
+
+/** Message spelt by the stars, drawn with `#` on `.` */
+pub fn part1(lights: &Input) -> Answer {
+  let time = message_time(lights);
+  let (min, max) = bounds(lights, time);
+  let width = (max.0 - min.0 + 1) as usize;
+  let height = (max.1 - min.1 + 1) as usize;
+  let mut sky = vec![vec![b'.'; width]; height];
+  for p in at(lights, time) {
+    sky[(p.1 - min.1) as usize][(p.0 - min.0) as usize] = b'#';
+  }
+  sky
+    .iter()
+    .map(|row| String::from_utf8_lossy(row))
+    .collect::<Vec<_>>()
+    .join("\n")
+    .into()
+}
+
+/** Seconds to wait for the message */
+pub fn part2(lights: &Input) -> Answer {
+  message_time(lights).into()
+}
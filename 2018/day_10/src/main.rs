@@ -1,30 +1,11 @@
-use std::error::Error;
-use std::io::{self, BufRead};
-use std::num::ParseIntError;
-
+use aoc::Output;
+use day_10::Light;
 use minifb::{Key, KeyRepeat, Window, WindowOptions};
-
-#[derive(Debug, Copy, Clone)]
-struct Vec2(i32, i32);
-
-#[derive(Debug, Copy, Clone)]
-struct Light(Vec2, Vec2);
+use std::{env, error::Error};
 
 const SCREEN_WIDTH: usize = 800;
 const SCREEN_HEIGHT: usize = 600;
 
-impl std::str::FromStr for Light {
-  type Err = ParseIntError;
-
-  fn from_str(line: &str) -> Result<Self, Self::Err> {
-    let pt_x: i32 = line[10..16].trim_start().parse().unwrap_or_default();
-    let pt_y: i32 = line[18..24].trim_start().parse().unwrap_or_default();
-    let d_x: i32 = line[36..38].trim_start().parse().unwrap_or_default();
-    let d_y: i32 = line[40..42].trim_start().parse().unwrap_or_default();
-    Ok(Light(Vec2(pt_x, pt_y), Vec2(d_x, d_y)))
-  }
-}
-
 struct Xform {
   sx: f32,
   sy: f32,
@@ -52,7 +33,7 @@ struct Xform {
 // Msky->screen = Tscreen->sky
 // scale = screen / sky; translate = screen_width/2, screen_height/2
 
-fn compute_xform(lights: &Vec<Light>) -> Xform {
+fn compute_xform(lights: &[Light]) -> Xform {
   let right: i32 = lights
     .iter()
     .max_by(|l, r| l.0 .0.cmp(&r.0 .0))
@@ -102,25 +83,22 @@ fn compute_xform(lights: &Vec<Light>) -> Xform {
   }
 }
 
-fn move_stars(lights: &mut Vec<Light>, speed: i32) {
+fn move_stars(lights: &mut [Light], speed: i32) {
   for star in lights.iter_mut() {
     star.0 .0 += speed * (star.1 .0);
     star.0 .1 += speed * (star.1 .1);
   }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-  let mut lights: Vec<Light> = io::stdin()
-    .lock()
-    .lines()
-    .map(|l| l.unwrap_or_default().parse().unwrap())
-    .collect();
-
-  let mut opts = WindowOptions::default();
-  opts.resize = true;
+/** Shows the sky in a window; Space pauses, ←/→ step a second when paused */
+fn view(mut lights: Vec<Light>) -> Result<(), Box<dyn Error>> {
+  let opts = WindowOptions {
+    resize: true,
+    ..WindowOptions::default()
+  };
   let mut wnd = Window::new("Stars Align", SCREEN_WIDTH, SCREEN_HEIGHT, opts)
-    .unwrap_or_else(|_| panic!("Window created failed!"));
-  wnd.limit_update_rate(Some(std::time::Duration::from_micros(16600)));
+    .map_err(|e| format!("Window creation failed: {}", e))?;
+  wnd.set_target_fps(60);
 
   let mut xform = compute_xform(&lights);
   let mut frame: Vec<u32> = vec![0; SCREEN_WIDTH * SCREEN_HEIGHT];
@@ -137,7 +115,7 @@ fn main() -> Result<(), Box<dyn Error>> {
       // translate then scale
       let x = ((star.0 .0 as f32 + xform.tx) * xform.sx).trunc() as usize;
       let y = ((star.0 .1 as f32 + xform.ty) * xform.sy).trunc() as usize;
-      frame[(y * SCREEN_WIDTH + x)] = 0xFF_FF_FF_FF;
+      frame[y * SCREEN_WIDTH + x] = 0xFF_FF_FF_FF;
     }
 
     wnd
@@ -169,3 +147,14 @@ fn main() -> Result<(), Box<dyn Error>> {
 
   Ok(())
 }
+
+fn main() -> Result<(), Box<dyn Error>> {
+  let input = day_10::parse(&aoc::read_stdin()?)?;
+  let out = Output::from_args(2018, 10)?;
+  if env::args().any(|arg| arg == "--view") {
+    return view(input);
+  }
+  out.part(1, "Message", || day_10::part1(&input));
+  out.part(2, "Seconds to wait", || day_10::part2(&input));
+  Ok(())
+}
@@ -0,0 +1,234 @@
+use aoc::Answer;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone)]
+struct Plants {
+  pot: VecDeque<bool>,
+  start_idx: i32,
+}
+
+impl Plants {
+  fn is_pot_planted(&self, idx: i32) -> bool {
+    let i = idx - self.start_idx;
+    match (i >= 0) && (i < self.pot.len() as i32) {
+      true => self.pot[i as usize],
+      false => false,
+    }
+  }
+
+  // Return |idx|-th pot’s configuration as an unsigned value where
+  // last five bits represent pot with its left and right neighbours
+  fn pot_configuration(&self, idx: i32) -> u8 {
+    ((idx - 2)..=(idx + 2))
+      .enumerate()
+      .map(|(idx, pot_idx)| (self.is_pot_planted(pot_idx) as u8) << (4 - idx))
+      // print binary with zero padding; 7 includes ‘0b’ prefix
+      // https://stackoverflow.com/a/44690529/183120
+      // .inspect(|x| println!("{:#07b}", x))
+      .fold(0, |acc, x| acc | x)
+  }
+
+  fn set(&mut self, idx: i32, value: bool) {
+    let mut adjusted_idx = idx - self.start_idx;
+    if adjusted_idx < 0 {
+      for _ in adjusted_idx..0 {
+        self.pot.push_front(false);
+      }
+      self.start_idx = idx;
+      adjusted_idx = 0;
+    } else if adjusted_idx >= self.pot.len() as i32 {
+      self.pot.resize(1 + adjusted_idx as usize, false);
+    }
+    self.pot[adjusted_idx as usize] = value;
+  }
+
+  fn front(&self) -> i32 {
+    self.start_idx
+  }
+
+  fn back(&self) -> i32 {
+    self.start_idx + self.pot.len() as i32 - 1
+  }
+
+  fn trim(&mut self) {
+    while !*self.pot.back().expect("Last can't be empty") {
+      self.pot.pop_back();
+    }
+    while !*self.pot.front().expect("First can't be empty") {
+      self.pot.pop_front();
+      self.start_idx += 1;
+    }
+  }
+
+  fn sum_planted_pot_id(&self) -> i32 {
+    self
+      .pot
+      .iter()
+      .enumerate()
+      .filter(|(_, &value)| value)
+      .map(|(idx, _)| self.start_idx + idx as i32)
+      .sum()
+  }
+}
+
+impl FromStr for Plants {
+  type Err = Box<dyn Error>;
+  fn from_str(input: &str) -> Result<Plants, Self::Err> {
+    let pots = input
+      .strip_prefix("initial state: ")
+      .ok_or("Expected `initial state: ` prefix")?;
+    Ok(Plants {
+      pot: pots.chars().map(pot).collect::<Result<_, _>>()?,
+      start_idx: 0,
+    })
+  }
+}
+
+impl fmt::Debug for Plants {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let indices: String = (self.start_idx
+      ..(self.start_idx + self.pot.len() as i32))
+      .map(|i| format!("{:3}", i))
+      .collect();
+    let values: String = self
+      .pot
+      .iter()
+      .map(|x| {
+        format!(
+          "  {}",
+          match x {
+            true => '#',
+            false => '.',
+          }
+        )
+      })
+      .collect();
+    write!(f, "{}\n{}", indices, values)
+  }
+}
+
+impl fmt::Display for Plants {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      self
+        .pot
+        .iter()
+        .map(|x| match x {
+          true => '#',
+          false => '.',
+        })
+        .collect::<String>()
+    )
+  }
+}
+
+fn pot(c: char) -> Result<bool, Box<dyn Error>> {
+  match c {
+    '#' => Ok(true),
+    '.' => Ok(false),
+    _ => Err(format!("Invalid pot `{}`", c).into()),
+  }
+}
+
+/// Initial pots and, for each 5-pot configuration, if the middle one germinates
+pub struct Input {
+  plants: Plants,
+  rules: [bool; 32],
+}
+
+/** Parses the initial state followed by `..#.. => #` rules */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let mut lines = input
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty());
+  let plants: Plants = match lines.next() {
+    Some((idx, line)) => line
+      .parse()
+      .map_err(|e| format!("{} at line {}", e, idx + 1))?,
+    None => return Err("Expected initial state!".into()),
+  };
+
+  let mut rules = [false; 32];
+  for (idx, line) in lines {
+    let rule = || -> Result<(usize, bool), Box<dyn Error>> {
+      let (from, to) = line.split_once(" => ").ok_or("Expected `=>`")?;
+      if from.len() != 5 || to.len() != 1 {
+        return Err("Expected 5 pots germinating 1".into());
+      }
+      let config = from
+        .chars()
+        .map(pot)
+        .try_fold(0, |acc, p| p.map(|p| acc << 1 | p as usize))?;
+      Ok((config, pot(to.chars().next().unwrap())?))
+    };
+    let (config, planted) =
+      rule().map_err(|e| format!("{} in `{}` at line {}", e, line, idx + 1))?;
+    rules[config] = planted;
+  }
+  if rules[0b00000] || rules[0b00001] || rules[0b10000] {
+    return Err("Pots far from plants mustn't germinate".into());
+  }
+  Ok(Input { plants, rules })
+}
+
+/** Sum of planted pot IDs after `count` generations */
+fn generations(plants: &Plants, rules: &[bool; 32], count: u64) -> i64 {
+  let mut cur_gen = plants.clone();
+  let mut next_gen = cur_gen.clone();
+  const SIMILARITY_THRESHOLD: u8 = 5;
+  let mut similarity_count = 0;
+  let mut short_circuit_gen = 0u64;
+  for gen in 1..=count {
+    // start checking for germination from current interval ± 3 pots
+    for i in (cur_gen.front() - 3)..=(cur_gen.back() + 3) {
+      next_gen.set(i, rules[cur_gen.pot_configuration(i) as usize]);
+    }
+    // remove needless elements due to previous set()s
+    next_gen.trim();
+
+    // try short-circuiting for very large generation |count|
+    if next_gen.pot == cur_gen.pot {
+      similarity_count += 1;
+      if similarity_count == SIMILARITY_THRESHOLD {
+        short_circuit_gen = gen;
+        break;
+      }
+    } else if similarity_count != 0 {
+      similarity_count = 0;
+    }
+
+    // avoid needless allocation; reuse same objects with their internals
+    std::mem::swap(&mut cur_gen, &mut next_gen);
+  }
+  let sum = cur_gen.sum_planted_pot_id() as i64;
+  if similarity_count == SIMILARITY_THRESHOLD {
+    aoc::debug_print!(
+      "Generational similarity exceeded threashold.  \
+       Stopped simulation at generation {} and short-circuited",
+      short_circuit_gen
+    );
+    // Calculate difference in sum of planted pot IDs b/w similar generations
+    let generational_delta =
+      next_gen.sum_planted_pot_id() - cur_gen.sum_planted_pot_id();
+    let pending_generations = (count - short_circuit_gen) as i64;
+    sum + pending_generations * generational_delta as i64
+  } else {
+    sum
+  }
+}
+
+/** Sum of planted pot IDs after 20 generations */
+pub fn part1(input: &Input) -> Answer {
+  generations(&input.plants, &input.rules, 20).into()
+}
+
+/** Sum of planted pot IDs after 50000000000 generations */
+pub fn part2(input: &Input) -> Answer {
+  generations(&input.plants, &input.rules, 50000000000).into()
+}
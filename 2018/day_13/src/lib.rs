@@ -0,0 +1,180 @@
+use aoc::{Answer, Grid, Point};
+use std::{cmp::Ordering, error::Error};
+
+#[derive(Copy, Clone, Debug)]
+enum Turn {
+  Left,
+  Straight,
+  Right,
+}
+
+#[derive(Clone, Debug)]
+struct Cart {
+  pos: Point,
+  velocity: Point,
+  last_turn: Turn,
+}
+
+impl Cart {
+  fn new(pos: Point, symbol: u8) -> Self {
+    let velocity = match symbol {
+      b'>' => Point(1, 0),
+      b'<' => Point(-1, 0),
+      b'v' => Point(0, 1),
+      b'^' => Point(0, -1),
+      _ => panic!("Unexpected card symbol"),
+    };
+    Cart {
+      pos,
+      velocity,
+      last_turn: Turn::Right,
+    }
+  }
+}
+
+// Custom ordering for Carts based on |pos|
+// https://www.philipdaniels.com/blog/2019/rust-equality-and-ordering/
+impl Ord for Cart {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.pos.cmp(&other.pos)
+  }
+}
+
+impl PartialOrd for Cart {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl PartialEq for Cart {
+  fn eq(&self, other: &Self) -> bool {
+    self.pos == other.pos
+  }
+}
+
+impl Eq for Cart {}
+
+fn update(map: &Grid<u8>, carts: &mut [Cart]) -> Option<Vec<Point>> {
+  let mut collision_pts = Vec::<Point>::new();
+  let n = carts.len();
+  for i in 0..n {
+    // Handle intra-step collisions; skip processing if an earlier processed
+    // cart had collided with this in the same step/update.
+    if collision_pts.contains(&carts[i].pos) {
+      continue;
+    }
+    let new_pos: Point = carts[i].pos + carts[i].velocity;
+    // Check for collision from next cart till previous cart circularly; as we
+    // simulate top-bottom-left-right, following carts are probable candidates;
+    // however last cart’s probably is first, so can’t skip any, cycle through.
+    // https://stackoverflow.com/a/59413981/183120
+    if carts
+      .iter()
+      .cycle()
+      .skip(i + 1) // start from next cart
+      .take(n - 1) // skip self
+      .any(|other| other.pos == new_pos)
+    {
+      collision_pts.push(new_pos);
+    }
+    let c = &mut carts[i];
+    c.pos = new_pos;
+    let ch = map[new_pos];
+    c.velocity = match (ch, c.velocity, c.last_turn) {
+      (b'/', Point(0, -1), _) | (b'\\', Point(0, 1), _) => Point(1, 0),
+      (b'/', Point(0, 1), _) | (b'\\', Point(0, -1), _) => Point(-1, 0),
+      (b'/', Point(1, 0), _) | (b'\\', Point(-1, 0), _) => Point(0, -1),
+      (b'/', Point(-1, 0), _) | (b'\\', Point(1, 0), _) => Point(0, 1),
+      (b'+', Point(1, 0), Turn::Right) => Point(0, -1),
+      (b'+', Point(-1, 0), Turn::Right) => Point(0, 1),
+      (b'+', Point(0, 1), Turn::Right) => Point(1, 0),
+      (b'+', Point(0, -1), Turn::Right) => Point(-1, 0),
+      (b'+', Point(1, 0), Turn::Straight) => Point(0, 1),
+      (b'+', Point(-1, 0), Turn::Straight) => Point(0, -1),
+      (b'+', Point(0, 1), Turn::Straight) => Point(-1, 0),
+      (b'+', Point(0, -1), Turn::Straight) => Point(1, 0),
+      _ => c.velocity,
+    };
+    c.last_turn = match (ch, c.last_turn) {
+      (b'+', Turn::Right) => Turn::Left,
+      (b'+', Turn::Left) => Turn::Straight,
+      (b'+', Turn::Straight) => Turn::Right,
+      _ => c.last_turn,
+    };
+  }
+  match collision_pts.is_empty() {
+    true => None,
+    false => Some(collision_pts),
+  }
+}
+
+const CART_SYMBOLS: &[u8; 4] = b"<>^v";
+
+/// Tracks with carts fished out and the carts in reading order
+pub struct Input {
+  map: Grid<u8>,
+  carts: Vec<Cart>,
+}
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let mut map = Grid::parse(input)?;
+  // fish carts out of map
+  let mut carts = Vec::<Cart>::new();
+  for pt in map.points() {
+    let ch = map[pt];
+    if CART_SYMBOLS.contains(&ch) {
+      map[pt] = match ch {
+        b'>' | b'<' => b'-',
+        _ => b'|',
+      };
+      carts.push(Cart::new(pt, ch));
+    } else if !b" -|/\\+".contains(&ch) {
+      return Err(
+        format!("Unexpected `{}` at line {}", ch as char, pt.1 + 1).into(),
+      );
+    }
+  }
+  Ok(Input { map, carts })
+}
+
+/** Runs carts till the first crash or, with `till_last`, till only one cart
+ * is left; returns the crash site or the last cart’s position */
+fn simulate(input: &Input, till_last: bool) -> Point {
+  let mut carts = input.carts.clone();
+  let mut ticks = 0;
+  loop {
+    assert!(carts.len() > 1, "No cart left standing");
+    // |carts| are already sorted by now (∀ iterations including 0-th)
+    if let Some(collisions) = update(&input.map, &mut carts) {
+      for pt in &collisions {
+        aoc::debug_print!("Collision by {}s at ({})", ticks, pt);
+      }
+      if !till_last {
+        return collisions[0];
+      }
+      // prune collided carts
+      carts.retain(|cart| collisions.iter().all(|&pt| pt != cart.pos));
+      if carts.len() == 1 {
+        return carts[0].pos;
+      }
+    }
+    carts.sort();
+    ticks += 1;
+  }
+}
+
+/** Location of the first crash.
+ *
+ * Panics if there are fewer than two carts.
+ */
+pub fn part1(input: &Input) -> Answer {
+  simulate(input, false).to_string().into()
+}
+
+/** Location of the last cart standing after all crashes.
+ *
+ * Panics if carts crash till none is left.
+ */
+pub fn part2(input: &Input) -> Answer {
+  simulate(input, true).to_string().into()
+}
@@ -0,0 +1,78 @@
+use aoc::Answer;
+use std::error::Error;
+
+const REQUIRED_RECIPES: usize = 10;
+
+/// Puzzle input both as a recipe count and as a sequence of scores
+pub struct Input {
+  count: usize,
+  sequence: Vec<u8>,
+}
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let input = input.trim();
+  if let Some(ch) = input.chars().find(|ch| !ch.is_ascii_digit()) {
+    return Err(format!("Expected only digits, found `{}`", ch).into());
+  }
+  let count = input
+    .parse()
+    .map_err(|e| format!("Failed to convert input into a number: {}", e))?;
+  let sequence = input.bytes().map(|ch| ch - b'0').collect();
+  Ok(Input { count, sequence })
+}
+
+fn digits(value: u8) -> (u8, Option<u8>) {
+  debug_assert!(value < 100);
+  match value < 10 {
+    true => (value, None),
+    false => (value / 10, Some(value % 10)),
+  }
+}
+
+/** Scores of the ten recipes after the first `count` recipes */
+pub fn part1(input: &Input) -> Answer {
+  let previous_recipes = input.count;
+  let total_recipes = previous_recipes + REQUIRED_RECIPES;
+  let mut recipes = vec![3u8, 7, 1, 0];
+  recipes.reserve(total_recipes);
+  let (mut i, mut j) = (0, 1);
+  // reduce two since they’re already in the vector; add 1 as per puzzle
+  while recipes.len() <= total_recipes {
+    let (n, opt_m) = digits(recipes[i] + recipes[j]);
+    recipes.push(n);
+    if let Some(m) = opt_m {
+      recipes.push(m);
+    }
+    i = (i + 1 + recipes[i] as usize) % recipes.len();
+    j = (j + 1 + recipes[j] as usize) % recipes.len();
+  }
+  recipes[previous_recipes..]
+    .iter()
+    .take(10) // explicitly take 10 as there’d be 11 if last iter added 2
+    .map(|&v| v.to_string())
+    .collect::<String>()
+    .into()
+}
+
+/** Recipes tried before arriving at the input’s score sequence */
+pub fn part2(input: &Input) -> Answer {
+  let recipe_sequence = &input.sequence;
+  let mut recipes = vec![3u8, 7, 1, 0];
+  let (mut i, mut j) = (0, 1);
+  loop {
+    let (n, opt_m) = digits(recipes[i] + recipes[j]);
+    recipes.push(n);
+    if recipes.ends_with(recipe_sequence) {
+      break;
+    }
+    if let Some(m) = opt_m {
+      recipes.push(m);
+      if recipes.ends_with(recipe_sequence) {
+        break;
+      }
+    }
+    i = (i + 1 + recipes[i] as usize) % recipes.len();
+    j = (j + 1 + recipes[j] as usize) % recipes.len();
+  }
+  (recipes.len() - recipe_sequence.len()).into()
+}
@@ -0,0 +1,19 @@
+fn error(input: &str) -> String {
+  match day_14::parse(input) {
+    Ok(_) => panic!("parsed {:?}", input),
+    Err(e) => e.to_string(),
+  }
+}
+
+#[test]
+fn digits_only() {
+  // `+` passes as part of a number but isn't a score
+  assert_eq!(error("+51589"), "Expected only digits, found `+`");
+  assert_eq!(error("515 89"), "Expected only digits, found ` `");
+}
+
+#[test]
+fn trims_surrounding_space() {
+  let input = day_14::parse(" 51589\n").unwrap();
+  assert_eq!(day_14::part2(&input), aoc::Answer::from(9u64));
+}
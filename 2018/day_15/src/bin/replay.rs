@@ -0,0 +1,20 @@
+use std::{
+  env,
+  error::Error,
+  fs,
+  io::{self, Write},
+};
+
+/** Replay the battle on the map at path given as argument; commands from
+ * stdin */
+fn main() -> Result<(), Box<dyn Error>> {
+  let path = env::args()
+    .nth(1)
+    .ok_or("Usage: replay MAP; `h` at prompt for help")?;
+  let input = day_15::parse(&fs::read_to_string(path)?)?;
+  let mut replay = day_15::replay(&input);
+  let mut out = io::stdout().lock();
+  replay.repl(io::stdin().lock(), &mut out)?;
+  writeln!(out)?;
+  Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::Point;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Faction {
+  Elf,
+  Goblin,
+}
+
+impl Display for Faction {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Faction::Elf => write!(f, "elf"),
+      Faction::Goblin => write!(f, "goblin"),
+    }
+  }
+}
+
+/// What a unit did on its turn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+  Move {
+    from: Point,
+    to: Point,
+  },
+  /// Hit unit `target`, taking `damage` hit points off it
+  Attack {
+    target: u32,
+    damage: u32,
+  },
+  /// Unit was killed; follows the attack that did it
+  Death,
+}
+
+/// One action of a battle.
+///
+/// `round` counts from 1; actions of the round the battle ends in carry its
+/// number though it isn’t a full round.  `pos` is where the unit stood when
+/// acting, so a move’s `from`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+  pub round: u32,
+  pub unit: u32,
+  pub kind: Faction,
+  pub pos: Point,
+  pub action: Action,
+}
+
+fn json_point(pt: Point) -> String {
+  format!("[{},{}]", pt.0, pt.1)
+}
+
+impl Event {
+  /** One-line JSON object; points are `[x, y]` */
+  pub fn to_json(&self) -> String {
+    let action = match self.action {
+      Action::Move { from, to } => format!(
+        "\"action\":\"move\",\"from\":{},\"to\":{}",
+        json_point(from),
+        json_point(to)
+      ),
+      Action::Attack { target, damage } => format!(
+        "\"action\":\"attack\",\"target\":{},\"damage\":{}",
+        target, damage
+      ),
+      Action::Death => "\"action\":\"death\"".to_string(),
+    };
+    format!(
+      "{{\"round\":{},\"unit\":{},\"kind\":\"{}\",\"pos\":{},{}}}",
+      self.round,
+      self.unit,
+      self.kind,
+      json_point(self.pos),
+      action
+    )
+  }
+}
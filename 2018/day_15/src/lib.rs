@@ -0,0 +1,842 @@
+mod event;
+mod replay;
+
+pub use aoc::Point;
+pub use event::{Action, Event, Faction};
+pub use replay::Replay;
+
+use aoc::{Answer, Grid};
+use core::fmt;
+use std::{
+  cmp::Ordering,
+  collections::{HashMap, VecDeque},
+  error::Error,
+  fmt::Display,
+  iter, mem,
+  num::NonZeroU32,
+  thread,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum FighterKind {
+  Elf(NonZeroU32),
+  Goblin(NonZeroU32),
+}
+
+impl FighterKind {
+  const INITIAL_ATTACK: u32 = 3;
+
+  fn new_elf(attack: u32) -> Self {
+    FighterKind::Elf(NonZeroU32::new(attack).unwrap())
+  }
+
+  fn new_goblin(attack: u32) -> Self {
+    FighterKind::Goblin(NonZeroU32::new(attack).unwrap())
+  }
+
+  fn attacks(&self) -> u32 {
+    match self {
+      FighterKind::Elf(attack) | FighterKind::Goblin(attack) => attack.get(),
+    }
+  }
+
+  fn faction(&self) -> Faction {
+    match self {
+      FighterKind::Elf(_) => Faction::Elf,
+      FighterKind::Goblin(_) => Faction::Goblin,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Fighter {
+  kind: FighterKind,
+  pos: Point,
+  hits: u32,
+}
+
+impl Ord for Fighter {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.pos.cmp(&other.pos)
+  }
+}
+
+impl PartialOrd for Fighter {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Fighter {
+  fn is_alive(&self) -> bool {
+    self.hits > 0
+  }
+
+  /** Returns next attacker ID if available */
+  fn target(&self, map: &Map) -> Option<u32> {
+    let mut enemies: Vec<u32> = map
+      .around(self.pos)
+      .filter(|&pt| {
+        matches!(map.cell(pt),
+                 Some(Cell::Occupied { kind, .. }) if kind != self.kind)
+      })
+      .map(|pt| map.layout[map.point_to_idx(pt)].get_fighter_id())
+      .collect();
+    // Fighter’s PartialOrd sorts only by |pos|; we want by |hits| first
+    enemies.sort_unstable_by_key(|idx| {
+      (map.fighters[idx].hits, map.fighters[idx].pos)
+    });
+    enemies.first().copied()
+  }
+}
+
+#[derive(Copy, Clone)]
+enum Cell {
+  Wall,
+  Vacant,
+  Occupied { kind: FighterKind, id: u32 },
+}
+
+impl Cell {
+  fn is_vacant(&self) -> bool {
+    matches!(self, Cell::Vacant)
+  }
+
+  fn get_fighter_id(&self) -> u32 {
+    match self {
+      Cell::Occupied { id, .. } => *id,
+      _ => unreachable!(),
+      // not going with panic! as never called on non-Occupied call
+    }
+  }
+
+  fn get_kind(&self) -> FighterKind {
+    match self {
+      Cell::Occupied { kind, .. } => *kind,
+      _ => unreachable!(),
+      // not going with panic! as never called on non-Occupied call
+    }
+  }
+
+  fn symbol(&self) -> u8 {
+    match self {
+      Cell::Wall => b'#',
+      // Destructuring structs when matching
+      // https://stackoverflow.com/q/41390457/183120
+      Cell::Vacant => b'.',
+      Cell::Occupied {
+        kind: FighterKind::Elf(_),
+        ..
+      } => b'E',
+      Cell::Occupied {
+        kind: FighterKind::Goblin(_),
+        ..
+      } => b'G',
+    }
+  }
+}
+
+impl fmt::Display for Cell {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol() as char)
+  }
+}
+
+// https://stackoverflow.com/a/62101709/183120
+fn clearscreen(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+  write!(f, "\x1B[2J\x1B[1;1H")
+}
+
+/// Steps to the nearest square in range and the index of that square
+type Nearest = (u32, usize);
+
+/// Distances from squares in range of a faction’s enemies, found by one
+/// breadth-first search from all of them at once and kept up to date as units
+/// move or die.
+#[derive(Clone)]
+struct Field {
+  faction: Faction,
+  /// Nearest square in range of each vacant square, the least index i.e.
+  /// first in reading order on ties; `None` for squares that aren’t vacant or
+  /// can’t reach any
+  nearest: Vec<Option<Nearest>>,
+}
+
+impl Field {
+  /** Whether `pt` is vacant and next to an enemy of the faction */
+  fn in_range(&self, map: &Map, pt: Point) -> bool {
+    map.is_vacant(pt)
+      && map.around(pt).any(|n| {
+        matches!(map.cell(n),
+                 Some(Cell::Occupied { kind, .. })
+                   if kind.faction() != self.faction)
+      })
+  }
+
+  fn new(map: &Map, faction: Faction) -> Self {
+    let mut nearest = vec![None; map.layout.len()];
+    let mut frontier = Vec::new();
+    let enemies = map
+      .fighters
+      .values()
+      .filter(|f| f.is_alive() && f.kind.faction() != faction);
+    for pt in enemies.flat_map(|f| f.pos.neighbours()) {
+      if map.is_vacant(pt) {
+        let idx = map.point_to_idx(pt);
+        if nearest[idx].is_none() {
+          nearest[idx] = Some((0, idx));
+          frontier.push(pt);
+        }
+      }
+    }
+    // a square’s entry is final once its distance’s layer is done
+    let mut next = Vec::new();
+    while !frontier.is_empty() {
+      for &pt in &frontier {
+        let (dist, target) = nearest[map.point_to_idx(pt)].unwrap();
+        for n in map.around(pt).filter(|&n| map.is_vacant(n)) {
+          match &mut nearest[map.point_to_idx(n)] {
+            slot @ None => {
+              *slot = Some((dist + 1, target));
+              next.push(n);
+            }
+            Some((d, t)) if *d == dist + 1 && *t > target => *t = target,
+            Some(_) => (),
+          }
+        }
+      }
+      mem::swap(&mut frontier, &mut next);
+      next.clear();
+    }
+    Field { faction, nearest }
+  }
+
+  /** Sets the entry of square `idx` if it’s closer; returns if it was */
+  fn offer(&mut self, idx: usize, entry: Nearest) -> bool {
+    let closer = self.nearest[idx].is_none_or(|old| entry < old);
+    if closer {
+      self.nearest[idx] = Some(entry);
+    }
+    closer
+  }
+
+  /** Brings the field up to date after `changed` squares turned vacant or
+   * not, or in range or not.
+   *
+   * Entries no longer following from a neighbour’s or from being in range are
+   * cleared first; as an entry follows from one a step less, going by steps
+   * finds all of them.  Those and `changed` are then searched afresh from the
+   * entries around them, as are any squares they bring closer.
+   */
+  fn update(&mut self, map: &Map, changed: &[Point]) {
+    let mut seeds = changed
+      .iter()
+      .map(|&pt| map.point_to_idx(pt))
+      .filter_map(|idx| self.nearest[idx].map(|entry| (entry, idx)))
+      .collect::<Vec<_>>();
+    seeds.sort_unstable();
+    let mut seeds = VecDeque::from(seeds);
+    let mut queue = VecDeque::new();
+    let mut cleared = Vec::new();
+    while let Some((entry, idx)) = pop_nearest(&mut seeds, &mut queue) {
+      // already cleared through another square
+      if self.nearest[idx] != Some(entry) {
+        continue;
+      }
+      let (dist, target) = entry;
+      let pt = map.idx_to_point(idx);
+      let holds = map.is_vacant(pt)
+        && match dist {
+          0 => self.in_range(map, pt),
+          _ => map.around(pt).any(|n| {
+            self.nearest[map.point_to_idx(n)] == Some((dist - 1, target))
+          }),
+        };
+      if !holds {
+        self.nearest[idx] = None;
+        cleared.push(idx);
+        // squares that may have followed from this one
+        let next = (dist + 1, target);
+        for n in map.around(pt) {
+          let n_idx = map.point_to_idx(n);
+          if self.nearest[n_idx] == Some(next) {
+            queue.push_back((next, n_idx));
+          }
+        }
+      }
+    }
+
+    let around = changed.iter().map(|&pt| map.point_to_idx(pt));
+    let mut seeds = Vec::new();
+    for idx in cleared.into_iter().chain(around) {
+      let pt = map.idx_to_point(idx);
+      if !map.is_vacant(pt) {
+        continue;
+      }
+      if self.in_range(map, pt) && self.offer(idx, (0, idx)) {
+        seeds.push(((0, idx), idx));
+      }
+      for n in map.around(pt) {
+        if let Some((dist, target)) = self.nearest[map.point_to_idx(n)] {
+          if self.offer(idx, (dist + 1, target)) {
+            seeds.push(((dist + 1, target), idx));
+          }
+        }
+      }
+    }
+    seeds.sort_unstable();
+    let mut seeds = VecDeque::from(seeds);
+    while let Some((entry, idx)) = pop_nearest(&mut seeds, &mut queue) {
+      // brought closer since
+      if self.nearest[idx] != Some(entry) {
+        continue;
+      }
+      let (dist, target) = entry;
+      let next = (dist + 1, target);
+      let pt = map.idx_to_point(idx);
+      for n in map.around(pt).filter(|&n| map.is_vacant(n)) {
+        let n_idx = map.point_to_idx(n);
+        if self.offer(n_idx, next) {
+          queue.push_back((next, n_idx));
+        }
+      }
+    }
+  }
+}
+
+/** Front entry of fewer steps of `a` and `b`, both in order of steps */
+fn pop_nearest(
+  a: &mut VecDeque<(Nearest, usize)>,
+  b: &mut VecDeque<(Nearest, usize)>,
+) -> Option<(Nearest, usize)> {
+  match (a.front(), b.front()) {
+    (Some(x), Some(y)) if y.0 .0 < x.0 .0 => b.pop_front(),
+    (Some(_), _) => a.pop_front(),
+    (None, _) => b.pop_front(),
+  }
+}
+
+/// Cavern layout with the fighters in it
+#[derive(Clone)]
+pub struct Map {
+  layout: Vec<Cell>,
+  fighters: HashMap<u32, Fighter>,
+  width: usize,
+  height: usize,
+  /// Actions so far when logging
+  log: Option<Vec<Event>>,
+  /// Field of each faction by `Faction as usize`, once needed
+  fields: [Option<Field>; 2],
+}
+
+impl Map {
+  fn point_to_idx(&self, pt: Point) -> usize {
+    self.width * pt.1 as usize + pt.0 as usize
+  }
+
+  fn idx_to_point(&self, idx: usize) -> Point {
+    Point((idx % self.width) as i32, (idx / self.width) as i32)
+  }
+
+  /** Squares next to `pt` on the map, in reading order */
+  fn around(&self, pt: Point) -> impl Iterator<Item = Point> + '_ {
+    pt.neighbours()
+      .into_iter()
+      .filter(|&n| self.cell(n).is_some())
+  }
+
+  /** Cell at `pt`; `None` off the map */
+  fn cell(&self, pt: Point) -> Option<Cell> {
+    let (x, y) = (usize::try_from(pt.0).ok()?, usize::try_from(pt.1).ok()?);
+    if x >= self.width || y >= self.height {
+      return None;
+    }
+    Some(self.layout[self.point_to_idx(pt)])
+  }
+
+  fn is_vacant(&self, pt: Point) -> bool {
+    matches!(self.cell(pt), Some(cell) if cell.is_vacant())
+  }
+
+  /** Logs what unit `idx` did in `round` if logging */
+  fn record(&mut self, round: u32, idx: u32, action: Action) {
+    if let Some(log) = &mut self.log {
+      let fighter = &self.fighters[&idx];
+      log.push(Event {
+        round,
+        unit: idx,
+        kind: fighter.kind.faction(),
+        pos: fighter.pos,
+        action,
+      });
+    }
+  }
+
+  /** Deduce possible next step for unit at `src`: towards the nearest
+   * square in range of an enemy, the first in reading order of those, along
+   * the shortest path starting with the first step in reading order */
+  fn next_step(&mut self, src: Point) -> Option<Point> {
+    let faction = self.layout[self.point_to_idx(src)].get_kind().faction();
+    let slot = faction as usize;
+    if self.fields[slot].is_none() {
+      self.fields[slot] = Some(Field::new(self, faction));
+    }
+    let nearest = &self.fields[slot].as_ref().unwrap().nearest;
+    let at = |pt| nearest[self.point_to_idx(pt)];
+    // distances are symmetric, so a neighbour’s nearest target is the unit’s
+    // nearest through it; ties go to the first target, then the first step
+    let best = self.around(src).filter_map(at).min();
+    best.and_then(|b| self.around(src).find(|&pt| at(pt) == Some(b)))
+  }
+
+  fn move_fighter(&mut self, idx: &u32, pt: Point) {
+    let old = self.fighters[idx].pos;
+    let old_idx = self.point_to_idx(old);
+    self.layout[old_idx] = Cell::Vacant;
+    // editing a value in a mutable hash map
+    // https://stackoverflow.com/a/30414450/183120
+    self.fighters.get_mut(idx).unwrap().pos = pt;
+    let new_idx = self.point_to_idx(pt);
+    self.layout[new_idx] = Cell::Occupied {
+      kind: self.fighters[idx].kind,
+      id: *idx,
+    };
+    self.refresh(&[old, pt]);
+  }
+
+  /** Updates fields found so far after units left or entered `squares`,
+   * which changes their neighbours being in range too */
+  fn refresh(&mut self, squares: &[Point]) {
+    let changed = squares
+      .iter()
+      .flat_map(|&pt| iter::once(pt).chain(pt.neighbours()))
+      .filter(|&pt| self.cell(pt).is_some())
+      .collect::<Vec<_>>();
+    let mut fields = mem::take(&mut self.fields);
+    for field in fields.iter_mut().flatten() {
+      field.update(self, &changed);
+    }
+    self.fields = fields;
+  }
+
+  /** Attack `unit`.  Return true if `unit` is dead after attack */
+  fn attack(&mut self, unit: u32, attacks: u32) -> bool {
+    self.fighters.get_mut(&unit).unwrap().hits =
+      self.fighters[&unit].hits.saturating_sub(attacks);
+    let attacked = &self.fighters[&unit];
+    let (pos, dead) = (attacked.pos, attacked.hits == 0);
+    if dead {
+      let idx = self.point_to_idx(pos);
+      self.layout[idx] = Cell::Vacant;
+      self.refresh(&[pos]);
+    }
+    dead
+  }
+
+  /** Set new attack points for Elves */
+  fn set_elves_attack(&mut self, new_attack: u32) {
+    // can’t mutate self.layout while mutating self.fighters; note and do later
+    // https://stackoverflow.com/a/45724688/183120
+    let mut to_update = Vec::new();
+    for fighter in self.fighters.values_mut() {
+      if matches!(fighter.kind, FighterKind::Elf(_)) {
+        fighter.kind = FighterKind::new_elf(new_attack);
+        to_update.push(fighter.pos);
+      }
+    }
+    for pt in to_update {
+      let idx = self.point_to_idx(pt);
+      let elf_id = self.layout[idx].get_fighter_id();
+      self.layout[idx] = Cell::Occupied {
+        kind: FighterKind::new_elf(new_attack),
+        id: elf_id,
+      };
+    }
+  }
+}
+
+impl Display for Map {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    clearscreen(f)?;
+    for row in 0..self.height {
+      for col in 0..self.width {
+        write!(f, "{}", self.layout[row * self.width + col])?;
+      }
+      writeln!(f)?;
+    }
+    Ok(())
+  }
+}
+
+struct RoundsAndHits(u32, u64);
+
+impl RoundsAndHits {
+  fn outcome(&self) -> u64 {
+    self.0 as u64 * self.1
+  }
+}
+
+fn battle(map: &mut Map, no_elf_dies: bool) -> Option<RoundsAndHits> {
+  battle_watched(map, no_elf_dies, |_, _| ())
+}
+
+/** `battle` handing the map to `watch` before every unit’s turn, with the
+ * unit’s id */
+fn battle_watched(
+  map: &mut Map,
+  no_elf_dies: bool,
+  mut watch: impl FnMut(&mut Map, u32),
+) -> Option<RoundsAndHits> {
+  let mut fighter_ids = map.fighters.keys().copied().collect::<Vec<_>>();
+  let mut rounds = 0u32;
+  let mut victory = false;
+  'battle: while !victory {
+    // fix turn order amongst fighters
+    fighter_ids.sort_unstable_by_key(|idx| map.fighters[idx].pos);
+    for idx in &fighter_ids {
+      // let dead warriors rest in peace
+      if map.fighters[idx].hits != 0 {
+        watch(map, *idx);
+        // A turn is not just a move or an attack but it can be move + attack
+        // when the move positions fighter next (“adjacent”) to an enemy.
+        // Move
+        if map.fighters[idx].target(map).is_none() {
+          match (map.next_step(map.fighters[idx].pos), victory) {
+            (Some(to), _) => {
+              let from = map.fighters[idx].pos;
+              map.record(rounds + 1, *idx, Action::Move { from, to });
+              map.move_fighter(idx, to);
+            }
+            // battle ceased in the middle of a round, don’t increment |rounds|
+            (None, true) => break 'battle,
+            (None, false) => (), // skip turn and lay in wait
+          }
+        }
+        // Attack
+        if let Some(enemy) = map.fighters[idx].target(map) {
+          let attacks = map.fighters[idx].kind.attacks();
+          let damage = attacks.min(map.fighters[&enemy].hits);
+          let action = Action::Attack {
+            target: enemy,
+            damage,
+          };
+          map.record(rounds + 1, *idx, action);
+          if map.attack(enemy, attacks) {
+            map.record(rounds + 1, enemy, Action::Death);
+            // enemy dead after attack; mark victory if no enemies are left
+            let enemy_kind = map.fighters[&enemy].kind;
+            if no_elf_dies && matches!(enemy_kind, FighterKind::Elf(_)) {
+              return None;
+            }
+            victory = !map
+              .fighters
+              .values()
+              .any(|f| f.is_alive() && f.kind == enemy_kind);
+          }
+        }
+      }
+    }
+    rounds += 1;
+  }
+  let hits_left = map.fighters.values().map(|f| f.hits as u64).sum::<u64>();
+  Some(RoundsAndHits(rounds, hits_left))
+}
+
+/// Hit points units start with; at least 1 each.
+#[derive(Copy, Clone, Debug)]
+pub struct Options {
+  pub elf_hits: u32,
+  pub goblin_hits: u32,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Options {
+      elf_hits: 200,
+      goblin_hits: 200,
+    }
+  }
+}
+
+pub type Input = Map;
+
+/** Parses a cavern of walls (`#`), open floor (`.`), elves (`E`) and goblins
+ * (`G`) */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  parse_with(input, Options::default())
+}
+
+/** `parse` with units starting on the hit points of `opts`; the map may be of
+ * any size and needn’t be walled in */
+pub fn parse_with(input: &str, opts: Options) -> Result<Input, Box<dyn Error>> {
+  if opts.elf_hits == 0 || opts.goblin_hits == 0 {
+    return Err("Units need at least 1 hit point".into());
+  }
+  let grid = Grid::parse(input)?;
+  let (width, height) = (grid.width(), grid.height());
+  let mut layout = Vec::<Cell>::with_capacity(width * height);
+  let mut fighters = HashMap::<u32, Fighter>::new();
+  for pt in grid.points() {
+    let symbol = grid[pt];
+    let id = u32::try_from(fighters.len())?;
+    let (kind, hits) = match symbol {
+      b'#' => {
+        layout.push(Cell::Wall);
+        continue;
+      }
+      b'.' => {
+        layout.push(Cell::Vacant);
+        continue;
+      }
+      b'E' => (
+        FighterKind::new_elf(FighterKind::INITIAL_ATTACK),
+        opts.elf_hits,
+      ),
+      b'G' => (
+        FighterKind::new_goblin(FighterKind::INITIAL_ATTACK),
+        opts.goblin_hits,
+      ),
+      _ => {
+        return Err(
+          format!("Unexpected `{}` at line {}", symbol as char, pt.1 + 1)
+            .into(),
+        )
+      }
+    };
+    fighters.insert(
+      id,
+      Fighter {
+        kind,
+        pos: pt,
+        hits,
+      },
+    );
+    layout.push(Cell::Occupied { kind, id });
+  }
+  Ok(Map {
+    layout,
+    fighters,
+    width,
+    height,
+    log: None,
+    fields: [None, None],
+  })
+}
+
+/** Outcome i.e. full rounds × hit points left of the battle as is */
+pub fn part1(map: &Input) -> Answer {
+  let result = battle(&mut map.clone(), /*no_elf_dies*/ false)
+    .expect("Battle can't be lost without a condition");
+  aoc::debug_print!("{} (rounds) × {} (hit points)", result.0, result.1);
+  result.outcome().into()
+}
+
+/** Battle with elves attacking with `elf_attack`; stops as soon as an elf
+ * dies */
+fn trial(map: &Map, elf_attack: u32) -> Option<RoundsAndHits> {
+  let mut map = map.clone();
+  map.set_elves_attack(elf_attack);
+  battle(&mut map, /*no_elf_dies*/ true)
+}
+
+/// How part 2 looks for the least elf attack letting no elf die.
+#[derive(Copy, Clone, Debug)]
+pub struct Search {
+  /// Trials run at once, each on a thread of its own
+  pub threads: usize,
+}
+
+impl Default for Search {
+  fn default() -> Self {
+    Search {
+      threads: thread::available_parallelism().map_or(1, |n| n.get()),
+    }
+  }
+}
+
+/** Outcome of the battle with the least elf attack where no elf dies; trials
+ * run on as many threads as there are cores.
+ *
+ * Panics if elves die even when killing any goblin with one attack.
+ */
+pub fn part2(map: &Input) -> Answer {
+  part2_with(map, Search::default())
+}
+
+/** `part2` searching as per `search` */
+pub fn part2_with(map: &Input, search: Search) -> Answer {
+  let goblin_hits = map
+    .fighters
+    .values()
+    .filter(|f| matches!(f.kind, FighterKind::Goblin(_)))
+    .map(|f| f.hits)
+    .max()
+    .unwrap_or(0);
+  let mut attacks = attacks(goblin_hits);
+  let threads = search.threads.max(1);
+  // elves surviving one attack may die with a stronger one (on this day's
+  // input they survive 14 and 15 but not 16 or 17), so every attack up to
+  // the answer gets a trial rather than bisecting
+  let (elf_attack, result) = iter::from_fn(|| {
+    let batch = attacks.by_ref().take(threads).collect::<Vec<_>>();
+    (!batch.is_empty()).then_some(batch)
+  })
+  .find_map(|batch| trials(map, &batch).into_iter().flatten().next())
+  .unwrap_or_else(|| {
+    panic!("Elves die even with {} attacks", goblin_hits.max(4))
+  });
+  aoc::debug_print!("With {} attacks no elves die!", elf_attack);
+  aoc::debug_print!("{} (rounds) × {} (hit points)", result.0, result.1);
+  result.outcome().into()
+}
+
+/** Elf attacks worth a trial against goblins of `hits` points, ascending
+ * from 4 to a one-hit kill.  Goblins start alike, so attacks killing one in
+ * as many hits play out alike; only the least of each is yielded, stepping
+ * straight to the next count of hits, so there are at most about `2√hits` */
+fn attacks(hits: u32) -> impl Iterator<Item = u32> {
+  iter::successors(Some(4), move |&attack| {
+    let blows = hits.div_ceil(attack);
+    // least attack killing in one blow fewer
+    (blows > 1).then(|| hits.div_ceil(blows - 1))
+  })
+}
+
+/** Trials of `attacks`, each on a thread, in the same order */
+fn trials(map: &Map, attacks: &[u32]) -> Vec<Option<(u32, RoundsAndHits)>> {
+  thread::scope(|scope| {
+    attacks
+      .iter()
+      .map(|&attack| {
+        scope.spawn(move || trial(map, attack).map(|result| (attack, result)))
+      })
+      .collect::<Vec<_>>()
+      .into_iter()
+      .map(|handle| handle.join().expect("Trial panicked"))
+      .collect()
+  })
+}
+
+/** Every action of the battle as is, in the order taken */
+pub fn events(map: &Input) -> Vec<Event> {
+  let mut map = map.clone();
+  map.log = Some(Vec::new());
+  battle(&mut map, /*no_elf_dies*/ false);
+  map.log.unwrap_or_default()
+}
+
+/** Battle as is, round by round, for viewing in a terminal */
+pub fn replay(map: &Input) -> Replay {
+  Replay::new(map, &events(map))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /** Steps to each square from `from` over vacant squares, by index */
+  fn distances(map: &Map, from: Point) -> Vec<Option<u32>> {
+    let mut dist = vec![None; map.layout.len()];
+    dist[map.point_to_idx(from)] = Some(0);
+    let mut queue = VecDeque::from([(from, 0)]);
+    while let Some((pt, d)) = queue.pop_front() {
+      for n in map.around(pt).filter(|&n| map.is_vacant(n)) {
+        let slot = &mut dist[map.point_to_idx(n)];
+        if slot.is_none() {
+          *slot = Some(d + 1);
+          queue.push_back((n, d + 1));
+        }
+      }
+    }
+    dist
+  }
+
+  /** Step of the unit at `src` by the puzzle’s rules, searching afresh:
+   * nearest square in range, first in reading order, then the first step in
+   * reading order of those starting a shortest path to it */
+  fn fresh_step(map: &Map, src: Point) -> Option<Point> {
+    let faction = map.cell(src)?.get_kind().faction();
+    let field = Field::new(map, faction);
+    let from_src = distances(map, src);
+    let (_, target) = (0..map.layout.len())
+      .map(|idx| map.idx_to_point(idx))
+      .filter(|&pt| pt != src && field.in_range(map, pt))
+      .filter_map(|pt| from_src[map.point_to_idx(pt)].map(|d| (d, pt)))
+      .min()?;
+    let to_target = distances(map, target);
+    map
+      .around(src)
+      .filter_map(|pt| to_target[map.point_to_idx(pt)].map(|d| (d, pt)))
+      .min()
+      .map(|(_, pt)| pt)
+  }
+
+  /** Fields of `map` found so far match fields built afresh */
+  fn check_fields(map: &Map, when: impl fmt::Display) {
+    for field in map.fields.iter().flatten() {
+      let fresh = Field::new(map, field.faction);
+      assert!(field.nearest == fresh.nearest, "fields {}", when);
+    }
+  }
+
+  /** Battle checking fields kept up to date before every turn and after the
+   * last, and that units about to move step as `fresh_step` says; it stops at
+   * an elf's death when elves attack with `elf_attack` */
+  fn check(map: &Map, elf_attack: Option<u32>) -> Option<RoundsAndHits> {
+    let mut map = map.clone();
+    if let Some(attack) = elf_attack {
+      map.set_elves_attack(attack);
+    }
+    let mut turns = 0;
+    let result = battle_watched(&mut map, elf_attack.is_some(), |map, idx| {
+      turns += 1;
+      check_fields(map, format_args!("before turn {}", turns));
+      let unit = map.fighters[&idx].clone();
+      if unit.target(map).is_none() {
+        let expected = fresh_step(map, unit.pos);
+        assert_eq!(map.next_step(unit.pos), expected, "turn {}", turns);
+      }
+    });
+    check_fields(&map, "after the battle");
+    result
+  }
+
+  /** `check` on the battle as is and with each elf attack part 2 tries till
+   * no elf dies */
+  fn check_both_parts(text: &str) {
+    let map = parse(text).unwrap();
+    check(&map, None).expect("Battle can't be lost without a condition");
+    attacks(200).find_map(|attack| check(&map, Some(attack)));
+  }
+
+  #[test]
+  fn fields_on_examples() {
+    for text in [
+      include_str!("../tests/maps/example_1.txt"),
+      include_str!("../tests/maps/example_2.txt"),
+      include_str!("../tests/maps/example_3.txt"),
+      include_str!("../tests/maps/example_4.txt"),
+      include_str!("../tests/maps/example_5.txt"),
+      include_str!("../tests/maps/example_6.txt"),
+      include_str!("../input/sample_1"),
+      include_str!("../input/sample_3"),
+    ] {
+      check_both_parts(text);
+    }
+  }
+
+  #[test]
+  fn fields_on_regression_maps() {
+    for text in [
+      include_str!("../tests/maps/first_step.txt"),
+      include_str!("../tests/maps/generated.txt"),
+    ] {
+      check(&parse(text).unwrap(), None);
+    }
+  }
+
+  #[test]
+  fn fields_on_input() {
+    check(&parse(include_str!("../input/input")).unwrap(), None);
+  }
+}
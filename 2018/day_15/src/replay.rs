@@ -0,0 +1,283 @@
+use crate::{clearscreen, Action, Event, Faction, Map, Point};
+use std::{
+  fmt::{self, Display, Formatter},
+  io::{self, BufRead, Write},
+  thread,
+  time::Duration,
+};
+
+#[derive(Copy, Clone)]
+struct Unit {
+  id: u32,
+  kind: Faction,
+  pos: Point,
+  hits: u32,
+}
+
+/// Cavern between rounds.
+#[derive(Clone)]
+struct Frame {
+  /// Rounds played, the last possibly in part
+  round: u32,
+  /// `#`, `.`, `E` or `G` per square
+  squares: Vec<u8>,
+  /// Units alive in reading order
+  units: Vec<Unit>,
+}
+
+impl Frame {
+  fn unit(&mut self, id: u32) -> Option<&mut Unit> {
+    self.units.iter_mut().find(|u| u.id == id)
+  }
+
+  fn apply(&mut self, event: &Event, width: usize) {
+    let idx = |pt: Point| pt.1 as usize * width + pt.0 as usize;
+    match event.action {
+      Action::Move { from, to } => {
+        if let Some(u) = self.unit(event.unit) {
+          u.pos = to;
+        }
+        self.squares.swap(idx(from), idx(to));
+      }
+      Action::Attack { target, damage } => {
+        if let Some(u) = self.unit(target) {
+          u.hits -= damage;
+        }
+      }
+      Action::Death => {
+        self.squares[idx(event.pos)] = b'.';
+        self.units.retain(|u| u.id != event.unit);
+      }
+    }
+  }
+}
+
+// ANSI colours for elves and goblins
+const GREEN: &str = "\x1B[32m";
+const RED: &str = "\x1B[31m";
+const RESET: &str = "\x1B[0m";
+
+fn colour(kind: Faction) -> &'static str {
+  match kind {
+    Faction::Elf => GREEN,
+    Faction::Goblin => RED,
+  }
+}
+
+const HELP: &str = "\
+n [COUNT]    next COUNT rounds (default 1)
+b [COUNT]    back COUNT rounds (default 1)
+g ROUND      go to round ROUND
+f            first round
+l            last round
+p [MS]       play to the end, MS milliseconds a round (default 200)
+h            help
+q            quit
+An empty line repeats the last command.";
+
+/// Battle round by round for stepping through in an ANSI terminal; the
+/// cavern is drawn with each unit’s hit points listed beside it.
+pub struct Replay {
+  frames: Vec<Frame>,
+  width: usize,
+  current: usize,
+}
+
+impl Replay {
+  /** Frames of `map` before the battle and after each round of it, rebuilt
+   * from the battle’s `events` */
+  pub fn new(map: &Map, events: &[Event]) -> Self {
+    let mut units = map
+      .fighters
+      .iter()
+      .map(|(&id, f)| Unit {
+        id,
+        kind: f.kind.faction(),
+        pos: f.pos,
+        hits: f.hits,
+      })
+      .collect::<Vec<_>>();
+    units.sort_unstable_by_key(|u| u.pos);
+    let mut frame = Frame {
+      round: 0,
+      squares: map.layout.iter().map(|c| c.symbol()).collect(),
+      units,
+    };
+    let mut frames = vec![frame.clone()];
+    for event in events {
+      if event.round > frame.round && frame.round > 0 {
+        frame.units.sort_unstable_by_key(|u| u.pos);
+        frames.push(frame.clone());
+      }
+      frame.round = event.round;
+      frame.apply(event, map.width);
+    }
+    if frame.round > 0 {
+      frame.units.sort_unstable_by_key(|u| u.pos);
+      frames.push(frame);
+    }
+    Replay {
+      frames,
+      width: map.width,
+      current: 0,
+    }
+  }
+
+  /** Rounds in the battle, the last possibly in part */
+  pub fn rounds(&self) -> u32 {
+    self.frames[self.frames.len() - 1].round
+  }
+
+  /** Go to the frame after `round`, or the last if the battle is over by
+   * then */
+  pub fn goto(&mut self, round: u32) {
+    self.current = self
+      .frames
+      .iter()
+      .position(|frame| frame.round >= round)
+      .unwrap_or(self.frames.len() - 1);
+  }
+
+  /** Move `count` rounds forward, or back if negative, staying in the
+   * battle */
+  pub fn step(&mut self, count: i64) {
+    let last = self.frames.len() as i64 - 1;
+    self.current = (self.current as i64 + count).clamp(0, last) as usize;
+  }
+
+  fn parse_count(token: Option<&str>, default: u64) -> Result<u64, String> {
+    match token {
+      Some(t) => t.parse().map_err(|_| "Expected a number".to_string()),
+      None => Ok(default),
+    }
+  }
+
+  /** Run one command; returns false when the user quits */
+  pub fn command(
+    &mut self,
+    line: &str,
+    out: &mut impl Write,
+  ) -> io::Result<bool> {
+    let mut tokens = line.split_ascii_whitespace();
+    let result = match tokens.next() {
+      Some("n") | Some("next") => {
+        Self::parse_count(tokens.next(), 1).map(|n| self.step(n as i64))
+      }
+      Some("b") | Some("back") => {
+        Self::parse_count(tokens.next(), 1).map(|n| self.step(-(n as i64)))
+      }
+      Some("g") | Some("goto") => tokens
+        .next()
+        .and_then(|t| t.parse().ok())
+        .map(|round| self.goto(round))
+        .ok_or_else(|| "Expected a round".to_string()),
+      Some("f") | Some("first") => {
+        self.current = 0;
+        Ok(())
+      }
+      Some("l") | Some("last") => {
+        self.current = self.frames.len() - 1;
+        Ok(())
+      }
+      Some("p") | Some("play") => match Self::parse_count(tokens.next(), 200) {
+        Ok(ms) => {
+          while self.current + 1 < self.frames.len() {
+            write!(out, "{}", self)?;
+            out.flush()?;
+            thread::sleep(Duration::from_millis(ms));
+            self.current += 1;
+          }
+          Ok(())
+        }
+        Err(e) => Err(e),
+      },
+      Some("h") | Some("help") => {
+        writeln!(out, "{}", HELP)?;
+        return Ok(true);
+      }
+      Some("q") | Some("quit") => return Ok(false),
+      Some(cmd) => Err(format!("Unknown command `{}`; try `h`", cmd)),
+      None => Ok(()),
+    };
+    write!(out, "{}", self)?;
+    if let Err(e) = result {
+      writeln!(out, "{}", e)?;
+    }
+    Ok(true)
+  }
+
+  /** Read commands from `input` till it ends or the user quits */
+  pub fn repl(
+    &mut self,
+    input: impl BufRead,
+    out: &mut impl Write,
+  ) -> io::Result<()> {
+    let mut last = String::new();
+    write!(out, "{}(replay) ", self)?;
+    out.flush()?;
+    for l in input.lines() {
+      let line = l?;
+      if !line.trim().is_empty() {
+        last = line;
+      }
+      if !self.command(&last, out)? {
+        break;
+      }
+      write!(out, "(replay) ")?;
+      out.flush()?;
+    }
+    Ok(())
+  }
+}
+
+/** Unit’s line in the side panel e.g. `G 12 (3, 4)  197 HP` */
+fn entry(f: &mut Formatter<'_>, unit: &Unit) -> fmt::Result {
+  let symbol = match unit.kind {
+    Faction::Elf => 'E',
+    Faction::Goblin => 'G',
+  };
+  write!(
+    f,
+    "{}{}{} {:>4} ({}, {}) {:>5} HP",
+    colour(unit.kind),
+    symbol,
+    RESET,
+    unit.id,
+    unit.pos.0,
+    unit.pos.1,
+    unit.hits
+  )
+}
+
+impl Display for Replay {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    clearscreen(f)?;
+    let frame = &self.frames[self.current];
+    let over = match self.current + 1 == self.frames.len() {
+      true => "; battle over",
+      false => "",
+    };
+    writeln!(f, "Round {} of {}{}", frame.round, self.rounds(), over)?;
+    let rows = frame.squares.chunks(self.width).collect::<Vec<_>>();
+    for line in 0..rows.len().max(frame.units.len()) {
+      match rows.get(line) {
+        Some(row) => {
+          for &square in row.iter() {
+            match square {
+              b'E' => write!(f, "{}E{}", GREEN, RESET)?,
+              b'G' => write!(f, "{}G{}", RED, RESET)?,
+              _ => write!(f, "{}", square as char)?,
+            }
+          }
+        }
+        None => write!(f, "{:1$}", "", self.width)?,
+      }
+      if let Some(unit) = frame.units.get(line) {
+        write!(f, "   ")?;
+        entry(f, unit)?;
+      }
+      writeln!(f)?;
+    }
+    Ok(())
+  }
+}
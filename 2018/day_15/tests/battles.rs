@@ -0,0 +1,87 @@
+use aoc::Answer;
+use day_15::{Action, Options, Point, Search};
+
+// puzzle's examples, with outcomes of part 1 and part 2 (none for the one
+// where elves get by without help)
+const BATTLES: [(&str, u64, Option<u64>); 6] = [
+  (include_str!("maps/example_1.txt"), 27730, Some(4988)),
+  (include_str!("maps/example_2.txt"), 36334, None),
+  (include_str!("maps/example_3.txt"), 39514, Some(31284)),
+  (include_str!("maps/example_4.txt"), 27755, Some(3478)),
+  (include_str!("maps/example_5.txt"), 28944, Some(6474)),
+  (include_str!("maps/example_6.txt"), 18740, Some(1140)),
+];
+
+#[test]
+fn part1_examples() {
+  for (map, outcome, _) in BATTLES {
+    let input = day_15::parse(map).unwrap();
+    assert_eq!(day_15::part1(&input), Answer::from(outcome), "{}", map);
+  }
+}
+
+#[test]
+fn part2_examples_any_threads() {
+  for (map, _, outcome) in BATTLES {
+    let Some(outcome) = outcome else { continue };
+    let input = day_15::parse(map).unwrap();
+    for threads in [1, 2, 7] {
+      let search = Search { threads };
+      let answer = day_15::part2_with(&input, search);
+      assert_eq!(
+        answer,
+        Answer::from(outcome),
+        "{} threads\n{}",
+        threads,
+        map
+      );
+    }
+  }
+}
+
+#[test]
+fn part2_tough_goblin() {
+  // a lone elf must kill within 67 blows, before taking 201 damage; only one
+  // attack per count of blows is tried, so the search stays small
+  let opts = Options {
+    goblin_hits: 1_000_000,
+    ..Options::default()
+  };
+  let input = day_15::parse_with("#####\n#EG.#\n#####", opts).unwrap();
+  let search = Search { threads: 4 };
+  assert_eq!(day_15::part2_with(&input, search), Answer::from(67u64 * 2));
+}
+
+#[test]
+fn first_step_in_reading_order() {
+  // squares right of and below the elf both start shortest paths to the
+  // square above the goblin; right comes first in reading order
+  let input = day_15::parse(include_str!("maps/first_step.txt")).unwrap();
+  let first = day_15::events(&input)[0];
+  assert_eq!(
+    first.action,
+    Action::Move {
+      from: Point(2, 1),
+      to: Point(3, 1)
+    }
+  );
+}
+
+#[test]
+fn generated_map() {
+  // first steps were once taken by backtracking through the least
+  // predecessor of each square, which in round 8 moved elf 0 down from
+  // (10, 4) though going right starts a path as short; outcomes checked
+  // against an independent simulation
+  let input = day_15::parse(include_str!("maps/generated.txt")).unwrap();
+  assert_eq!(day_15::part1(&input), Answer::from(6204u64));
+  assert_eq!(day_15::part2(&input), Answer::from(29403u64));
+}
+
+#[test]
+fn unwalled_cavern() {
+  // squares on the edge have neighbours off the map; the goblin steps next
+  // to the elf and strikes first every round, killing it in round 67
+  let input = day_15::parse("G.E").unwrap();
+  assert_eq!(day_15::part1(&input), Answer::from(67u64 * 2));
+}
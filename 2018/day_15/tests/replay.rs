@@ -0,0 +1,95 @@
+use day_15::Replay;
+
+// 47 full rounds; the 48th ends on the first unit finding no targets
+const MAP: &str = include_str!("maps/example_1.txt");
+
+fn replay() -> Replay {
+  day_15::replay(&day_15::parse(MAP).unwrap())
+}
+
+/** Round shown after running `commands` one by one, with any error the last
+ * one printed */
+fn run(replay: &mut Replay, commands: &[&str]) -> (String, Option<String>) {
+  let mut text = String::new();
+  for command in commands {
+    let mut out = Vec::new();
+    assert!(replay.command(command, &mut out).unwrap(), "{}", command);
+    text = String::from_utf8(out).unwrap();
+  }
+  let shown = replay.to_string();
+  let round = shown.lines().next().unwrap();
+  let round = &round[round.find("Round ").unwrap()..];
+  // the error follows the drawing of the cavern and its units
+  let error = text.strip_prefix(&shown).unwrap().lines().next();
+  (round.to_string(), error.map(str::to_string))
+}
+
+#[test]
+fn frames_of_rounds() {
+  let mut r = replay();
+  assert_eq!(r.rounds(), 47);
+  let round = |r: &mut Replay, commands: &[&str]| run(r, commands).0;
+  assert_eq!(round(&mut r, &[""]), "Round 0 of 47");
+  assert_eq!(round(&mut r, &["n"]), "Round 1 of 47");
+  assert_eq!(round(&mut r, &["n 5", "next"]), "Round 7 of 47");
+  assert_eq!(round(&mut r, &["b 2"]), "Round 5 of 47");
+  assert_eq!(round(&mut r, &["back 10"]), "Round 0 of 47");
+  assert_eq!(round(&mut r, &["g 23"]), "Round 23 of 47");
+  assert_eq!(round(&mut r, &["n 100"]), "Round 47 of 47; battle over");
+  assert_eq!(round(&mut r, &["g 0", "l"]), "Round 47 of 47; battle over");
+  assert_eq!(round(&mut r, &["f"]), "Round 0 of 47");
+  assert_eq!(round(&mut r, &["goto 1000"]), "Round 47 of 47; battle over");
+  assert_eq!(round(&mut r, &["p 0"]), "Round 47 of 47; battle over");
+  assert_eq!(
+    round(&mut r, &["first", "last"]),
+    "Round 47 of 47; battle over"
+  );
+}
+
+#[test]
+fn invalid_commands() {
+  let mut r = replay();
+  r.goto(3);
+  for (command, error) in [
+    ("n x", "Expected a number"),
+    ("b -1", "Expected a number"),
+    ("g", "Expected a round"),
+    ("g 1.5", "Expected a round"),
+    ("p fast", "Expected a number"),
+    ("frob", "Unknown command `frob`; try `h`"),
+  ] {
+    let (round, found) = run(&mut r, &[command]);
+    assert_eq!(found.as_deref(), Some(error), "{}", command);
+    // and the replay stays where it was
+    assert_eq!(round, "Round 3 of 47", "{}", command);
+  }
+  assert!(!r.command("q", &mut Vec::new()).unwrap());
+}
+
+#[test]
+fn units_per_round() {
+  let mut r = replay();
+  // the goblin at (2, 1) moves to (3, 1) and the elf at (4, 2) takes a blow
+  // in round 1
+  let text = r.to_string();
+  assert!(text.contains("(2, 1)   200 HP"), "{}", text);
+  assert!(text.contains("(4, 2)   200 HP"), "{}", text);
+  run(&mut r, &["n"]);
+  let text = r.to_string();
+  assert!(text.contains("(3, 1)   200 HP"), "{}", text);
+  assert!(text.contains("(4, 2)   197 HP"), "{}", text);
+  run(&mut r, &["b"]);
+  assert_eq!(r.to_string(), replay().to_string());
+}
+
+#[test]
+fn repl_repeats_last_command() {
+  let mut r = replay();
+  let mut out = Vec::new();
+  r.repl("n 2\n\n \nb\nq\nn\n".as_bytes(), &mut out).unwrap();
+  // the blank lines move two rounds on each, and nothing runs after quitting
+  let text = String::from_utf8(out).unwrap();
+  assert_eq!(text.matches("(replay) ").count(), 5);
+  let (round, _) = run(&mut r, &[""]);
+  assert_eq!(round, "Round 5 of 47");
+}
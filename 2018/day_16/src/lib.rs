@@ -0,0 +1,205 @@
+mod parse;
+mod resolve;
+
+use aoc::{debug_print, Answer};
+use elfcode::{Instruction, Op, OpcodeMap, Program};
+use parse::Sample;
+use std::{error::Error, fmt::Write};
+
+const REGISTERS: usize = 4;
+type Cpu = elfcode::Cpu<u16, REGISTERS>;
+
+// assignments listed when samples are ambiguous
+const MAX_ASSIGNMENTS: usize = 16;
+// registers of made-up samples suggested when samples are ambiguous
+const PROBES: [[u16; REGISTERS]; 3] =
+  [[9, 4, 7, 2], [3, 8, 1, 6], [5, 5, 2, 9]];
+
+/// Samples with opcodes resolved from them and the program decoded with those
+pub struct Input {
+  samples: Vec<Sample>,
+  opcodes: OpcodeMap,
+  program: Program<u16>,
+}
+
+impl Input {
+  /** Opcode map resolved from the samples; days 19 and 21 can use it */
+  pub fn opcodes(&self) -> &OpcodeMap {
+    &self.opcodes
+  }
+}
+
+/** Operations fitting `sample` as a bit set */
+fn possible_opcodes(cpu: &mut Cpu, sample: Sample) -> u16 {
+  let [_, a, b, c] = sample.instr;
+  Op::ALL
+    .iter()
+    .filter(|&&op| {
+      cpu.reg = sample.pre;
+      // a trap e.g. on a register out of range rules out the operation
+      cpu.execute(&Instruction::new(op, a, b, c)).is_ok()
+        && cpu.reg == sample.post
+    })
+    .fold(0, |mask, &op| mask | 1u16 << op as u16)
+}
+
+/** Fewest made-up samples of `opcode` whose `After:` registers tell apart
+ * the operations in `ops`, as `Before:` registers and instruction.  They
+ * write register 0 from registers or values 0-3, on one of `PROBES`. */
+fn telling_samples(
+  cpu: &mut Cpu,
+  opcode: usize,
+  ops: &[Op],
+) -> Vec<([u16; REGISTERS], [u16; 4])> {
+  let pool = PROBES
+    .iter()
+    .flat_map(|&pre| {
+      (0..4)
+        .flat_map(move |a| (0..4).map(move |b| (pre, [opcode as u16, a, b, 0])))
+    })
+    .collect::<Vec<_>>();
+  // `After:` registers of each operation on each sample
+  let posts = pool
+    .iter()
+    .map(|&(pre, [_, a, b, c])| {
+      ops
+        .iter()
+        .map(|&op| {
+          cpu.reg = pre;
+          cpu
+            .execute(&Instruction::new(op, a, b, c))
+            .ok()
+            .map(|_| cpu.reg)
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+  let tells_apart = |picks: &Vec<usize>| {
+    let mut seen = (0..ops.len())
+      .map(|op| picks.iter().map(|&i| posts[i][op]).collect::<Vec<_>>())
+      .collect::<Vec<_>>();
+    seen.sort_unstable();
+    seen.windows(2).all(|pair| pair[0] != pair[1])
+  };
+  let n = pool.len();
+  let singles = (0..n).map(|i| vec![i]);
+  let pairs = (0..n).flat_map(|i| (i + 1..n).map(move |j| vec![i, j]));
+  let triples = (0..n).flat_map(|i| {
+    (i + 1..n).flat_map(move |j| (j + 1..n).map(move |k| vec![i, j, k]))
+  });
+  singles
+    .chain(pairs)
+    .chain(triples)
+    .find(tells_apart)
+    .expect("Three samples tell any operations apart")
+    .into_iter()
+    .map(|i| pool[i])
+    .collect()
+}
+
+/** Opcode assignment consistent with all samples; errors list assignments,
+ * ambiguous opcodes and the fewest extra samples to settle them when the
+ * samples don't settle on one */
+fn resolve(samples: &[Sample]) -> Result<OpcodeMap, Box<dyn Error>> {
+  let mut cpu = Cpu::new();
+  // opcodes absent from samples could be any operation
+  let mut opcode_to_fnptr = [u16::MAX; 16];
+  for &sample in samples {
+    // an opcode's operation has to fit all of its samples
+    opcode_to_fnptr[sample.instr[0] as usize] &=
+      possible_opcodes(&mut cpu, sample);
+  }
+  let options = resolve::options(&opcode_to_fnptr)
+    .ok_or("Inconsistent data: no opcode assignment fits the sample set")?;
+  if options.iter().all(|ops| ops.count_ones() == 1) {
+    let ops = options.map(|ops| Op::ALL[ops.trailing_zeros() as usize]);
+    return OpcodeMap::new(ops);
+  }
+  let found = resolve::assignments(&opcode_to_fnptr, MAX_ASSIGNMENTS + 1);
+  let more = match found.len() > MAX_ASSIGNMENTS {
+    true => " (first few shown)",
+    false => "",
+  };
+  let mut msg =
+    String::from("Insufficient data: can't resolve opcodes from sample set");
+  writeln!(msg, "\nConsistent opcode assignments{}:", more)?;
+  for opcodes in found.iter().take(MAX_ASSIGNMENTS) {
+    let names = opcodes.iter().map(|op| op.name()).collect::<Vec<_>>();
+    writeln!(msg, "  {}", names.join(" "))?;
+  }
+  write!(msg, "Opcodes needing more samples:")?;
+  let ambiguities = resolve::ambiguities(&opcode_to_fnptr);
+  for (opcode, ops) in &ambiguities {
+    let names = ops.iter().map(|op| op.name()).collect::<Vec<_>>();
+    write!(msg, "\n  {}: {}", opcode, names.join(" | "))?;
+  }
+  // knowing these opcodes' operations settles the rest
+  write!(
+    msg,
+    "\nSamples settling all opcodes, once run for `After:`:"
+  )?;
+  for opcode in resolve::deciding_opcodes(&opcode_to_fnptr) {
+    let (_, ops) = ambiguities
+      .iter()
+      .find(|(ambiguous, _)| *ambiguous == opcode)
+      .expect("Deciding opcodes are ambiguous");
+    for (pre, [opcode, a, b, c]) in telling_samples(&mut cpu, opcode, ops) {
+      write!(msg, "\n  Before: {:?}\n  {} {} {} {}", pre, opcode, a, b, c)?;
+    }
+  }
+  Err(msg.into())
+}
+
+/** Parses samples and the program, resolving opcodes from the samples */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let parse::Input { samples, program } = parse::parse(input)?;
+  let opcodes = resolve(&samples)?;
+  debug_print!("Opcodes: {:?}", opcodes);
+
+  let is_reg = |r: u16| (r as usize) < REGISTERS;
+  let instructions = program
+    .iter()
+    .map(|&(line, [opcode, a, b, c])| {
+      let op = opcodes.op(opcode as usize).ok_or_else(|| {
+        format!("Unknown opcode {} in program at line {}", opcode, line)
+      })?;
+      if !is_reg(c)
+        || (op.reads_a() && !is_reg(a))
+        || (op.reads_b() && !is_reg(b))
+      {
+        return Err(format!("Invalid register in program at line {}", line));
+      }
+      Ok(Instruction::new(op, a, b, c))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(Input {
+    samples,
+    opcodes,
+    program: Program {
+      ip_reg: None,
+      instructions,
+    },
+  })
+}
+
+/** Samples behaving like three or more opcodes */
+pub fn part1(input: &Input) -> Answer {
+  let mut cpu = Cpu::new();
+  input
+    .samples
+    .iter()
+    .filter(|&&sample| possible_opcodes(&mut cpu, sample).count_ones() > 2)
+    .count()
+    .into()
+}
+
+/** Register 0 after running the program */
+pub fn part2(input: &Input) -> Answer {
+  let mut cpu = Cpu::new();
+  cpu
+    .load(input.program.clone())
+    .expect("Program has no IP binding");
+  // registers are checked in `parse` and wrapping arithmetic can't trap
+  cpu.run().expect("Program trapped");
+  cpu.reg[0].into()
+}
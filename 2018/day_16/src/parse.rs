@@ -0,0 +1,99 @@
+use elfcode::Op;
+use std::{convert::TryFrom, error::Error};
+
+/// Instruction with the registers before and after running it.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Sample {
+  pub pre: [u16; 4],
+  pub instr: [u16; 4],
+  pub post: [u16; 4],
+}
+
+/// Puzzle input: samples followed by a program of numeric instructions.
+#[derive(Default, Debug)]
+pub struct Input {
+  pub samples: Vec<Sample>,
+  /// Instructions with the lines they're on
+  pub program: Vec<(usize, [u16; 4])>,
+}
+
+fn error(line: usize, msg: impl AsRef<str>) -> Box<dyn Error> {
+  format!("{} at line {}", msg.as_ref(), line).into()
+}
+
+/** Four values from `tokens`, naming the `what` that's malformed */
+fn values<'a>(
+  tokens: impl Iterator<Item = &'a str>,
+  what: &str,
+  line: usize,
+) -> Result<[u16; 4], Box<dyn Error>> {
+  let values = tokens
+    .map(|t| {
+      t.parse::<u16>()
+        .map_err(|_| error(line, format!("Invalid value `{}` in {}", t, what)))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  <[u16; 4]>::try_from(values).map_err(|v| {
+    error(
+      line,
+      format!("Expected 4 values in {}, found {}", what, v.len()),
+    )
+  })
+}
+
+/** Registers of a `Before:` or `After:` line e.g. `[14, 0, 2, 1]` */
+fn registers(
+  text: &str,
+  label: &str,
+  line: usize,
+) -> Result<[u16; 4], Box<dyn Error>> {
+  let list = text
+    .strip_prefix(label)
+    .map(str::trim)
+    .and_then(|s| s.strip_prefix('['))
+    .and_then(|s| s.strip_suffix(']'))
+    .ok_or_else(|| error(line, format!("Expected `{} [a, b, c, d]`", label)))?;
+  let what = format!("`{}` line", label);
+  values(list.split(',').map(str::trim), &what, line)
+}
+
+/** Instruction e.g. `9 2 1 2`; its opcode must be one of `Op::COUNT` */
+fn instruction(text: &str, line: usize) -> Result<[u16; 4], Box<dyn Error>> {
+  let instr = values(text.split_ascii_whitespace(), "instruction", line)?;
+  if instr[0] as usize >= Op::COUNT {
+    return Err(error(
+      line,
+      format!("Opcode {} out of range 0-{}", instr[0], Op::COUNT - 1),
+    ));
+  }
+  Ok(instr)
+}
+
+/// Parses samples, each a `Before:` line, an instruction and an `After:`
+/// line, then the program; blank lines and spacing within lines are free.
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let mut parsed = Input::default();
+  let mut lines = input
+    .lines()
+    .enumerate()
+    .map(|(idx, text)| (idx + 1, text.trim()))
+    .filter(|(_, text)| !text.is_empty());
+  while let Some((line, text)) = lines.next() {
+    if !text.starts_with("Before:") {
+      parsed.program.push((line, instruction(text, line)?));
+      continue;
+    }
+    let pre = registers(text, "Before:", line)?;
+    let mut next = |expected: &str| {
+      lines.next().ok_or_else(|| {
+        error(line, format!("Sample ends before its {}", expected))
+      })
+    };
+    let (line, text) = next("instruction")?;
+    let instr = instruction(text, line)?;
+    let (line, text) = next("`After:` line")?;
+    let post = registers(text, "After:", line)?;
+    parsed.samples.push(Sample { pre, instr, post });
+  }
+  Ok(parsed)
+}
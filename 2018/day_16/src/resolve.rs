@@ -0,0 +1,221 @@
+use elfcode::Op;
+
+/// Operation of each opcode
+pub type Assignment = [Op; Op::COUNT];
+
+/// Bit set of operations for each opcode, bit `op as u16` standing for `op`.
+///
+/// `candidates[opcode]` has the operations fitting every sample seen for
+/// `opcode`; an assignment maps each opcode to a distinct candidate, i.e. it
+/// is a perfect matching between opcodes and operations.
+pub type Candidates = [u16; Op::COUNT];
+
+// assignments tried per group of entangled opcodes when looking for the
+// fewest telling them apart
+const MAX_GROUP_ASSIGNMENTS: usize = 256;
+
+/** Assignment consistent with `candidates` if there's any, found by growing
+ * a matching one augmenting path at a time */
+pub fn matching(candidates: &Candidates) -> Option<Assignment> {
+  // opcode taking each operation so far
+  let mut owner = [None; Op::COUNT];
+  for opcode in 0..Op::COUNT {
+    if !augment(candidates, opcode, &mut owner, &mut 0) {
+      return None;
+    }
+  }
+  let mut ops = [Op::ALL[0]; Op::COUNT];
+  for (op, opcode) in owner.iter().enumerate() {
+    ops[opcode.expect("Every opcode is matched")] = Op::ALL[op];
+  }
+  Some(ops)
+}
+
+/** Finds `opcode` an operation, moving owners of operations it wants onto
+ * others of theirs; `seen` has the operations visited on this path */
+fn augment(
+  candidates: &Candidates,
+  opcode: usize,
+  owner: &mut [Option<usize>; Op::COUNT],
+  seen: &mut u16,
+) -> bool {
+  let mut options = candidates[opcode];
+  while options != 0 {
+    let op = options.trailing_zeros() as usize;
+    options &= options - 1;
+    if *seen & 1 << op != 0 {
+      continue;
+    }
+    *seen |= 1 << op;
+    let free = match owner[op] {
+      Some(other) => augment(candidates, other, owner, seen),
+      None => true,
+    };
+    if free {
+      owner[op] = Some(opcode);
+      return true;
+    }
+  }
+  false
+}
+
+/** Operations each opcode takes in some assignment consistent with
+ * `candidates`, or `None` when there's no such assignment.
+ *
+ * Given one assignment, opcode `x` can take the operation of opcode `y`
+ * exactly when `x`'s own can be handed on round to `y`: some `z` having it
+ * as a candidate takes it, another takes `z`'s and so on, until `y` takes
+ * one and the cycle closes. */
+pub fn options(candidates: &Candidates) -> Option<Candidates> {
+  let ops = matching(candidates)?;
+  let bit = |opcode: usize| 1u16 << ops[opcode] as u16;
+  // opcodes each one can hand its operation on to, transitively
+  let mut reach = [0u16; Op::COUNT];
+  for (x, &set) in candidates.iter().enumerate() {
+    for (y, takers) in reach.iter_mut().enumerate() {
+      if set & bit(y) != 0 {
+        *takers |= 1 << x;
+      }
+    }
+  }
+  for via in 0..Op::COUNT {
+    for x in 0..Op::COUNT {
+      if reach[x] & 1 << via != 0 {
+        reach[x] |= reach[via];
+      }
+    }
+  }
+  let mut options = [0; Op::COUNT];
+  for (x, options) in options.iter_mut().enumerate() {
+    *options = (0..Op::COUNT)
+      .filter(|&y| candidates[x] & bit(y) != 0 && reach[x] & 1 << y != 0)
+      .fold(bit(x), |set, y| set | bit(y));
+  }
+  Some(options)
+}
+
+/** All assignments consistent with `candidates`, at most `limit` of them;
+ * every choice tried leads to one, so this takes time in proportion to the
+ * assignments listed */
+pub fn assignments(candidates: &Candidates, limit: usize) -> Vec<Assignment> {
+  let mut found = Vec::new();
+  if let Some(options) = options(candidates) {
+    list(options, 0, limit, &mut found);
+  }
+  found
+}
+
+/** Adds assignments fixing opcodes from `opcode` on to `found`, each of
+ * `options` being part of one */
+fn list(
+  options: Candidates,
+  opcode: usize,
+  limit: usize,
+  found: &mut Vec<Assignment>,
+) {
+  if opcode == Op::COUNT {
+    found.push(options.map(|set| Op::ALL[set.trailing_zeros() as usize]));
+    return;
+  }
+  let mut choices = options[opcode];
+  while choices != 0 && found.len() < limit {
+    let op = choices & choices.wrapping_neg();
+    choices &= choices - 1;
+    let mut fixed = options;
+    fixed[opcode] = op;
+    let fixed = self::options(&fixed).expect("Every option is feasible");
+    list(fixed, opcode + 1, limit, found);
+  }
+}
+
+/** Opcodes taking more than one operation across all assignments consistent
+ * with `candidates`, with those operations; these need extra samples */
+pub fn ambiguities(candidates: &Candidates) -> Vec<(usize, Vec<Op>)> {
+  let options = match options(candidates) {
+    Some(options) => options,
+    None => return Vec::new(),
+  };
+  (0..Op::COUNT)
+    .filter(|&opcode| options[opcode].count_ones() > 1)
+    .map(|opcode| {
+      let ops = Op::ALL
+        .iter()
+        .filter(|&&op| options[opcode] & 1 << op as u16 != 0)
+        .copied()
+        .collect();
+      (opcode, ops)
+    })
+    .collect()
+}
+
+/** Fewest opcodes whose operations, once known, settle all the others, by
+ * group of opcodes sharing operations they could take; a group with more
+ * than `MAX_GROUP_ASSIGNMENTS` assignments gets all but one of its opcodes,
+ * which is always enough but may not be fewest */
+pub fn deciding_opcodes(candidates: &Candidates) -> Vec<usize> {
+  let (options, ops) = match (options(candidates), matching(candidates)) {
+    (Some(options), Some(ops)) => (options, ops),
+    _ => return Vec::new(),
+  };
+  let mut decided = Vec::new();
+  let mut left = (0..Op::COUNT)
+    .filter(|&opcode| options[opcode].count_ones() > 1)
+    .fold(0u16, |set, opcode| set | 1 << opcode);
+  while left != 0 {
+    // grow a group from its least opcode through shared operations
+    let mut group = left & left.wrapping_neg();
+    loop {
+      let shared = members(group).fold(0, |set, opcode| set | options[opcode]);
+      let grown = members(left)
+        .filter(|&opcode| options[opcode] & shared != 0)
+        .fold(0, |set, opcode| set | 1 << opcode);
+      if grown == group {
+        break;
+      }
+      group = grown;
+    }
+    left &= !group;
+    // the group's assignments with opcodes elsewhere fixed as in `ops`
+    let mut fixed = options;
+    for opcode in members(!group) {
+      fixed[opcode] = 1 << ops[opcode] as u16;
+    }
+    let found = assignments(&fixed, MAX_GROUP_ASSIGNMENTS + 1);
+    let members = members(group).collect::<Vec<_>>();
+    decided.extend(match found.len() > MAX_GROUP_ASSIGNMENTS {
+      true => members[1..].to_vec(),
+      false => telling_subset(&members, &found),
+    });
+  }
+  decided.sort_unstable();
+  decided
+}
+
+/** Opcodes in the bit set `set` */
+fn members(set: u16) -> impl Iterator<Item = usize> {
+  (0..Op::COUNT).filter(move |&opcode| set & 1 << opcode != 0)
+}
+
+/** Fewest of `members` on which no two of `found` agree */
+fn telling_subset(members: &[usize], found: &[Assignment]) -> Vec<usize> {
+  let subsets = 1u32 << members.len();
+  (1..members.len())
+    .flat_map(|size| {
+      (1..subsets).filter(move |subset| subset.count_ones() as usize == size)
+    })
+    .map(|subset| {
+      (0..members.len())
+        .filter(|i| subset & 1 << i != 0)
+        .map(|i| members[i])
+        .collect::<Vec<_>>()
+    })
+    .find(|subset| {
+      let mut keys = found
+        .iter()
+        .map(|ops| subset.iter().fold(0u64, |key, &i| key << 4 | ops[i] as u64))
+        .collect::<Vec<_>>();
+      keys.sort_unstable();
+      keys.windows(2).all(|pair| pair[0] != pair[1])
+    })
+    .unwrap_or_else(|| members[1..].to_vec())
+}
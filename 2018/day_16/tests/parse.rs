@@ -0,0 +1,36 @@
+fn error(input: &str) -> String {
+  match day_16::parse(input) {
+    Ok(_) => panic!("parsed {:?}", input),
+    Err(e) => e.to_string(),
+  }
+}
+
+#[test]
+fn sample_opcode_out_of_range() {
+  let input = "Before: [3, 2, 1, 1]\n20 2 1 2\nAfter:  [3, 2, 2, 1]\n";
+  assert_eq!(error(input), "Opcode 20 out of range 0-15 at line 2");
+}
+
+#[test]
+fn program_opcode_out_of_range() {
+  let input =
+    "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n\n\n16 2 1 2\n";
+  assert_eq!(error(input), "Opcode 16 out of range 0-15 at line 6");
+}
+
+#[test]
+fn program_register_out_of_range() {
+  // line numbers count the samples and blank lines before the program
+  let input = format!(
+    "{}\n\n\n0 0 0 0\n\n1 2 3 4\n",
+    include_str!("../input/input")
+      .split("\n\n\n\n")
+      .next()
+      .unwrap()
+  );
+  let line = input.lines().count();
+  assert_eq!(
+    error(&input),
+    format!("Invalid register in program at line {}", line)
+  );
+}
@@ -0,0 +1,180 @@
+use elfcode::{Cpu, Instruction, Op, OpcodeMap};
+use std::collections::BTreeSet;
+
+fn error(input: &str) -> String {
+  match day_16::parse(input) {
+    Ok(_) => panic!("parsed {:?}", input),
+    Err(e) => e.to_string(),
+  }
+}
+
+/** Sample of `opcode a b c` on registers `pre`, run with `map` */
+fn sample(map: &OpcodeMap, pre: [u16; 4], instr: [u16; 4]) -> String {
+  let [opcode, a, b, c] = instr;
+  let mut cpu = Cpu::<u16, 4>::new();
+  cpu.reg = pre;
+  let op = map.op(opcode as usize).unwrap();
+  cpu.execute(&Instruction::new(op, a, b, c)).unwrap();
+  format!(
+    "Before: {:?}\n{} {} {} {}\nAfter:  {:?}\n\n",
+    pre, opcode, a, b, c, cpu.reg
+  )
+}
+
+/** Samples of every opcode run with the default map, enough to pin each
+ * down, but only those `keep` lets through */
+fn samples(keep: impl Fn(&Op, [u16; 4], [u16; 4]) -> bool) -> String {
+  let map = OpcodeMap::default();
+  let mut text = String::new();
+  for opcode in 0..Op::COUNT as u16 {
+    let op = map.op(opcode as usize).unwrap();
+    for pre in [[9, 4, 7, 2], [3, 8, 1, 6], [5, 5, 2, 9], [0, 1, 2, 3]] {
+      for (a, b) in (0..4).flat_map(|a| (0..4).map(move |b| (a, b))) {
+        let instr = [opcode, a, b, (a + b) % 4];
+        if keep(&op, pre, instr) {
+          text += &sample(&map, pre, instr);
+        }
+      }
+    }
+  }
+  text + "\n\n0 0 0 0\n"
+}
+
+/** Samples suggested by `msg`, run with the default map */
+fn suggested(msg: &str) -> String {
+  let (_, list) = msg.split_once("once run for `After:`:").unwrap();
+  let lines = list.lines().map(str::trim).collect::<Vec<_>>();
+  lines[1..]
+    .chunks(2)
+    .map(|pair| {
+      let regs = pair[0]
+        .strip_prefix("Before: [")
+        .and_then(|regs| regs.strip_suffix(']'))
+        .unwrap();
+      let value = |v: &str| v.trim().parse().unwrap();
+      let pre =
+        <[u16; 4]>::try_from(regs.split(',').map(value).collect::<Vec<_>>());
+      let instr = pair[1].split(' ').map(value).collect::<Vec<_>>();
+      let instr = <[u16; 4]>::try_from(instr);
+      sample(&OpcodeMap::default(), pre.unwrap(), instr.unwrap())
+    })
+    .collect()
+}
+
+/** Opcodes sampled in `text` */
+fn opcodes(text: &str) -> BTreeSet<&str> {
+  text
+    .lines()
+    .filter(|line| !line.contains(':') && !line.is_empty())
+    .map(|line| line.split(' ').next().unwrap())
+    .collect()
+}
+
+#[test]
+fn generated_samples_resolve() {
+  let input = day_16::parse(&samples(|_, _, _| true)).unwrap();
+  assert_eq!(
+    input.opcodes().to_string(),
+    OpcodeMap::default().to_string()
+  );
+}
+
+#[test]
+fn ambiguous_samples() {
+  // `addr` and `addi` agree when register b holds b
+  let input = samples(|&op, pre, [_, _, b, _]| {
+    !matches!(op, Op::Addr | Op::Addi) || pre[b as usize] == b
+  });
+  let map = OpcodeMap::default();
+  let [addr, addi] = [Op::Addr, Op::Addi]
+    .map(|op| (0..Op::COUNT).find(|&i| map.op(i) == Some(op)).unwrap());
+  let names = |swap: bool| {
+    (0..Op::COUNT)
+      .map(|opcode| {
+        let opcode = match (swap, opcode) {
+          (true, _) if opcode == addr => addi,
+          (true, _) if opcode == addi => addr,
+          _ => opcode,
+        };
+        map.op(opcode).unwrap().name()
+      })
+      .collect::<Vec<_>>()
+      .join(" ")
+  };
+  let msg = error(&input);
+  let mut listed = msg.lines().skip(2).take(2).collect::<Vec<_>>();
+  listed.sort_unstable();
+  let mut expected = [names(false), names(true)].map(|l| format!("  {}", l));
+  expected.sort_unstable();
+  assert_eq!(listed, expected, "{}", msg);
+  let (low, high) = (addr.min(addi), addr.max(addi));
+  let tail = format!(
+    "Opcodes needing more samples:\n  {}: addr | addi\n  {}: addr | addi\n\
+     Samples settling all opcodes, once run for `After:`:\n",
+    low, high
+  );
+  assert!(msg.contains(&tail), "{}", msg);
+  // one sample of one of them is enough
+  let extra = suggested(&msg);
+  assert_eq!(extra.matches("Before").count(), 1, "{}", msg);
+  let settled = day_16::parse(&(extra + &input)).unwrap();
+  assert_eq!(settled.opcodes().to_string(), map.to_string());
+}
+
+#[test]
+fn opcodes_without_samples() {
+  // four opcodes could be any of four operations: 24 assignments, settled by
+  // knowing three of them
+  let input = samples(|_, _, [opcode, ..]| opcode % 4 != 1);
+  let msg = error(&input);
+  assert!(msg.contains("(first few shown)"), "{}", msg);
+  for opcode in [1, 5, 9, 13] {
+    assert!(msg.contains(&format!("\n  {}: ", opcode)), "{}", msg);
+  }
+  let extra = suggested(&msg);
+  assert_eq!(opcodes(&extra).len(), 3, "{}", msg);
+  let settled = day_16::parse(&(extra + &input)).unwrap();
+  assert_eq!(
+    settled.opcodes().to_string(),
+    OpcodeMap::default().to_string()
+  );
+}
+
+#[test]
+fn no_samples_at_all() {
+  // samples come for all opcodes but one, the last being what's left
+  let msg = error("0 0 0 0\n");
+  let extra = suggested(&msg);
+  assert_eq!(opcodes(&extra).len(), Op::COUNT - 1);
+  let settled = day_16::parse(&(extra + "0 0 0 0\n")).unwrap();
+  assert_eq!(
+    settled.opcodes().to_string(),
+    OpcodeMap::default().to_string()
+  );
+}
+
+#[test]
+fn no_operation_fits_sample() {
+  let input = "Before: [0, 0, 0, 0]\n0 0 0 0\nAfter:  [5, 5, 5, 5]\n";
+  assert_eq!(
+    error(input),
+    "Inconsistent data: no opcode assignment fits the sample set"
+  );
+}
+
+#[test]
+fn opcodes_sharing_their_only_operation() {
+  // only `seti` puts 3 into register 0 here, and two opcodes can't both
+  // be `seti`
+  let sample = |opcode| {
+    format!(
+      "Before: [0, 0, 0, 0]\n{} 3 0 0\nAfter:  [3, 0, 0, 0]\n",
+      opcode
+    )
+  };
+  let input = format!("{}\n{}", sample(0), sample(1));
+  assert_eq!(
+    error(&input),
+    "Inconsistent data: no opcode assignment fits the sample set"
+  );
+}
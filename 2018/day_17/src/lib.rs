@@ -0,0 +1,330 @@
+use aoc::{Answer, Point};
+use core::str::FromStr;
+use std::{
+  error::Error,
+  fmt::{self, Debug, Formatter},
+  fs::File,
+  io::Write,
+  thread::sleep,
+  time::Duration,
+};
+
+/// Vein of clay along a row or a column
+#[derive(Debug, Default)]
+pub struct Line {
+  end: [Point; 2],
+}
+
+impl Line {
+  /** Return Line with varying X, but stable Y */
+  fn new_dx(fixed: i32, min: i32, max: i32) -> Self {
+    Line {
+      end: [Point(min, fixed), Point(max, fixed)],
+    }
+  }
+
+  /** Return Line with varying Y, but stable X */
+  fn new_dy(fixed: i32, min: i32, max: i32) -> Self {
+    Line {
+      end: [Point(fixed, min), Point(fixed, max)],
+    }
+  }
+
+  fn offset_by(&self, p: Point) -> Self {
+    Line {
+      end: [self.end[0] - p, self.end[1] - p],
+    }
+  }
+
+  fn is_horizontal(&self) -> bool {
+    self.end[0].1 == self.end[1].1
+  }
+}
+
+impl FromStr for Line {
+  type Err = Box<dyn Error>;
+
+  /** Parses `x=495, y=2..7` or `y=7, x=495..501` */
+  fn from_str(input: &str) -> Result<Line, Self::Err> {
+    let invalid = || format!("Invalid vein `{}`", input);
+    let (fixed, range) = input.split_once(", ").ok_or_else(invalid)?;
+    let (axis, fixed) = fixed.split_once('=').ok_or_else(invalid)?;
+    let (other, range) = range.split_once('=').ok_or_else(invalid)?;
+    let (min, max) = range.split_once("..").ok_or_else(invalid)?;
+    let (fixed, min, max) = (fixed.parse()?, min.parse()?, max.parse()?);
+    if min > max || min < 0 || fixed < 0 {
+      return Err(invalid().into());
+    }
+    match (axis, other) {
+      ("x", "y") => Ok(Line::new_dy(fixed, min, max)),
+      ("y", "x") => Ok(Line::new_dx(fixed, min, max)),
+      _ => Err(invalid().into()),
+    }
+  }
+}
+
+struct Ground {
+  cols: i32,
+  rows: i32,
+  data: Vec<u8>,
+}
+
+impl Debug for Ground {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "\x1B[2J\x1B[1;1H")?;
+    for r in 0..self.rows {
+      for c in 0..self.cols {
+        write!(f, "{}", char::from(self.data[self.to_idx(Point(c, r))]))?;
+      }
+      writeln!(f)?;
+    }
+    sleep(Duration::from_secs_f32(0.25));
+    Ok(())
+  }
+}
+
+impl Ground {
+  fn to_idx(&self, pt: Point) -> usize {
+    (pt.1 * self.cols + pt.0) as usize
+  }
+
+  fn set(&mut self, ch: u8, l: Line) {
+    match l.is_horizontal() {
+      true => {
+        let idx = self.to_idx(l.end[0]);
+        self.data[idx..=(idx + (l.end[1].0 - l.end[0].0) as usize)].fill(ch);
+      }
+      false => {
+        let offset = self.to_idx(l.end[0]);
+        self.data[offset..]
+          .iter_mut()
+          .step_by(self.cols as usize)
+          .take((l.end[1].1 - l.end[0].1 + 1) as usize)
+          .for_each(|c| *c = ch);
+      }
+    }
+  }
+
+  fn set_point(&mut self, p: Point, ch: u8) {
+    let idx = self.to_idx(p);
+    self.data[idx] = ch;
+  }
+
+  fn get_point(&self, p: Point) -> u8 {
+    let idx = self.to_idx(p);
+    self.data[idx]
+  }
+
+  fn find_ground(&self, mut p: Point) -> Point {
+    let c = self.get_point(p);
+    // keep skipping until we reach a different block like ‘#’, ‘~’
+    // or ‘|’ in case we reach another stream’s ebb out
+    while p.1 < self.rows && self.get_point(p) == c {
+      p += Point::DOWN;
+    }
+    p + Point::UP
+  }
+
+  /**
+    Given one wall of a pot, find other.  Requires wet (‘|’) intervening blocks
+    and blocking underlying blocks (wall or water; ‘#’ or ‘~’). `dir` should be
+    `-1` for searching left.  Returns (_, false) if it’s not a barrier plane.
+  */
+  fn opposite_wall(&self, mut p: Point, dir: i32) -> (Point, bool) {
+    let mut below = p + Point::DOWN;
+    while (self.get_point(p) != b'#')  // skip ‘.’ and ‘|’
+      && ((self.get_point(below) == b'#') || (self.get_point(below) == b'~'))
+    {
+      p += Point(dir, 0);
+      below = p + Point::DOWN;
+    }
+    match (self.get_point(p), self.get_point(below)) {
+      (b'#', b'#') | (b'#', b'~') => (p - Point(dir, 0), true),
+      _ => (p, false),
+    }
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum State {
+  Down,
+  Fill,
+  Wait(u8), // state while children are at work; may/may not resume
+  Done,
+  Gone,
+}
+
+struct Stream {
+  state: State,
+  to_fill: u16,
+  pos: Point,
+  parent: i32,
+}
+
+impl Stream {
+  fn new(pos: Point, parent: i32) -> Self {
+    Stream {
+      state: State::Down,
+      pos,
+      to_fill: 0,
+      parent,
+    }
+  }
+
+  fn is_alive(&self) -> bool {
+    match self.state {
+      State::Down | State::Fill => true,
+      State::Done | State::Wait(_) | State::Gone => false,
+    }
+  }
+
+  fn flow(&mut self, idx: usize, g: &mut Ground, new_streams: &mut Vec<Self>) {
+    match self.state {
+      State::Down => {
+        let bottom = g.find_ground(self.pos + Point::DOWN);
+        g.set(b'|', Line::new_dy(self.pos.0, self.pos.1, bottom.1));
+        let distance = bottom.1 - self.pos.1;
+        self.pos = bottom;
+        self.state = match (bottom.1 + 1) < g.rows {
+          true => match g.get_point(self.pos + Point::DOWN) {
+            b'|' => State::Gone,
+            _ => {
+              self.to_fill = distance as u16;
+              State::Fill
+            }
+          },
+          false => State::Gone, // reached end of input
+        };
+      }
+      State::Fill => {
+        let (left, wall_l) = g.opposite_wall(self.pos, -1);
+        let (right, wall_r) = g.opposite_wall(self.pos, 1);
+        match (wall_l, wall_r) {
+          (true, true) => {
+            g.set(b'~', Line::new_dx(self.pos.1, left.0, right.0));
+            self.pos += Point::UP;
+            self.to_fill -= 1;
+            // done with stream; unblock parent stream
+            if self.to_fill == 0 {
+              self.state = State::Done;
+            }
+          }
+          (true, false) => {
+            g.set(b'|', Line::new_dx(self.pos.1, left.0, right.0));
+            new_streams.push(Stream::new(right, idx as i32));
+            self.state = State::Wait(1);
+          }
+          (false, true) => {
+            g.set(b'|', Line::new_dx(self.pos.1, left.0, right.0));
+            new_streams.push(Stream::new(left, idx as i32));
+            self.state = State::Wait(1);
+          }
+          (false, false) => {
+            // both arms beget children
+            g.set(b'|', Line::new_dx(self.pos.1, left.0, right.0));
+            new_streams.push(Stream::new(left, idx as i32));
+            new_streams.push(Stream::new(right, idx as i32));
+            self.state = State::Wait(2);
+          }
+        }
+      }
+      _ => (),
+    }
+  }
+}
+
+#[allow(dead_code)] // handy to inspect the ground when debugging
+fn log_to_file(ground: &Ground) -> Result<(), Box<dyn Error>> {
+  let mut o = File::create("output")?;
+  for j in 0..ground.rows {
+    for i in 0..ground.cols {
+      let idx = (j * ground.cols + i) as usize;
+      write!(o, "{}", ground.data[idx] as char)?;
+    }
+    writeln!(o)?;
+  }
+  Ok(())
+}
+
+/// Clay veins from the scan
+pub type Input = Vec<Line>;
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let lines: Vec<Line> = aoc::parse_lines(input)?;
+  match lines.is_empty() {
+    true => Err("No clay veins in the scan".into()),
+    false => Ok(lines),
+  }
+}
+
+/** Ground after water from the spring settles */
+fn flow(lines: &[Line]) -> Ground {
+  let (mut min, mut max) =
+    (Point(i32::MAX, i32::MAX), Point(i32::MIN, i32::MIN));
+  for l in lines {
+    min.0 = min.0.min(l.end[0].0.min(l.end[1].0));
+    min.1 = min.1.min(l.end[0].1.min(l.end[1].1));
+    max.0 = max.0.max(l.end[0].0.max(l.end[1].0));
+    max.1 = max.1.max(l.end[0].1.max(l.end[1].1));
+  }
+  min.0 -= 1;
+  max.0 += 1;
+
+  let rows = max.1 - min.1 + 1;
+  let cols = max.0 - min.0 + 1;
+  let mut ground = Ground {
+    cols,
+    rows,
+    data: vec![b'.'; (rows * cols) as usize],
+  };
+
+  // plot the scan
+  for line in lines {
+    let l = line.offset_by(min);
+    ground.set(b'#', l);
+  }
+  // set first stream
+  let eternal_spring = Point(500 - min.0, 0);
+  let mut streams = Vec::with_capacity(200_000);
+  streams.push(Stream::new(eternal_spring, -1));
+  ground.set_point(eternal_spring, b'|');
+
+  let mut new_streams = Vec::with_capacity(32);
+  while streams.iter().any(|s| s.is_alive()) {
+    let n = streams.len();
+    for idx in 0..n {
+      if streams[idx].state != State::Done {
+        streams[idx].flow(idx, &mut ground, &mut new_streams);
+        if streams[idx].state == State::Done && streams[idx].parent >= 0 {
+          let parent_id = streams[idx].parent as usize;
+          streams[parent_id].state = match streams[parent_id].state {
+            State::Wait(1) => State::Fill,
+            State::Wait(child) => State::Wait(child - 1),
+            _ => streams[parent_id].state,
+          };
+        }
+      }
+    }
+    streams.append(&mut new_streams);
+  }
+
+  ground
+}
+
+/** Tiles water reaches */
+pub fn part1(lines: &Input) -> Answer {
+  let ground = flow(lines);
+  ground
+    .data
+    .iter()
+    .filter(|&&c| c == b'|' || c == b'~')
+    .count()
+    .into()
+}
+
+/** Tiles water is left standing in after the spring dries */
+pub fn part2(lines: &Input) -> Answer {
+  let ground = flow(lines);
+  // log_to_file(&ground)?;
+  ground.data.iter().filter(|&&c| c == b'~').count().into()
+}
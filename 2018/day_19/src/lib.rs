@@ -0,0 +1,66 @@
+use aoc::Answer;
+use elfcode::{Compiled, Idiom, Image, Options};
+use std::error::Error;
+
+pub type Word = u32;
+pub type Cpu = elfcode::Cpu<Word, 6>;
+
+/// Program with its IP bound and the loops worth shortcutting
+pub struct Input {
+  image: Image<Word, 6>,
+  idioms: Vec<Idiom>,
+}
+
+impl Input {
+  /** CPU with the program loaded, set to the chosen overflow mode */
+  pub fn cpu(&self) -> Cpu {
+    self.image.cpu()
+  }
+
+  /** Known loops found; these run in one go instead of being interpreted */
+  pub fn idioms(&self) -> &[Idiom] {
+    &self.idioms
+  }
+}
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  parse_with(input, Options::default())
+}
+
+/** Program read as per `opts`; see `Image::parse` */
+pub fn parse_with(input: &str, opts: Options) -> Result<Input, Box<dyn Error>> {
+  let image = Image::parse(input, &opts)?;
+  // Program is trying to factorize a large number inefficiently!
+  let idioms = Idiom::find(&image.program.instructions, image.program.ip_reg);
+  Ok(Input { image, idioms })
+}
+
+/** Register 0 after running the program starting with `r0` in it */
+pub fn run(input: &Input, r0: Word) -> Result<Word, Box<dyn Error>> {
+  let mut cpu = input.cpu();
+  let entries = input.idioms.iter().map(|i| i.entry).collect::<Vec<_>>();
+  let compiled = Compiled::new(&cpu, &entries)?;
+  cpu.reg[0] = r0;
+  while !cpu.is_halted() {
+    if !input.idioms.iter().any(|i| i.apply(&mut cpu)) {
+      compiled.run(&mut cpu)?;
+    }
+  }
+  Ok(cpu.reg[0])
+}
+
+/** Register 0 after running the program.
+ *
+ * Panics if the program traps; only `Overflow::Checked` lets it.
+ */
+pub fn part1(input: &Input) -> Answer {
+  run(input, 0).expect("Program trapped").into()
+}
+
+/** Register 0 after running the program with register 0 set to 1.
+ *
+ * Panics if the program traps; only `Overflow::Checked` lets it.
+ */
+pub fn part2(input: &Input) -> Answer {
+  run(input, 1).expect("Program trapped").into()
+}
@@ -0,0 +1,192 @@
+use aoc::{Answer, Grid, Point};
+use std::collections::{hash_map::Entry, HashMap};
+use std::error::Error;
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+
+/// Rooms and doors of the facility
+pub struct Map {
+  grid: Grid<u8>,
+}
+
+impl Map {
+  fn new(dim: Point, pos: Point, input: &str) -> Self {
+    let mut m = Map {
+      grid: Grid::new(dim.0 as usize, dim.1 as usize, b'#'),
+    };
+    m.parse(pos, input);
+    m
+  }
+
+  fn parse(&mut self, mut pos: Point, input: &str) {
+    self.set(pos, b'X');
+    let mut fork_points = Vec::with_capacity(128);
+    for c in input.bytes() {
+      match c {
+        b'N' => {
+          self.set(pos + Point(0, -1), b'-');
+          self.set(pos + Point(0, -2), b'.');
+          pos += Point(0, -2)
+        }
+        b'S' => {
+          self.set(pos + Point(0, 1), b'-');
+          self.set(pos + Point(0, 2), b'.');
+          pos += Point(0, 2)
+        }
+        b'W' => {
+          self.set(pos + Point(-1, 0), b'|');
+          self.set(pos + Point(-2, 0), b'.');
+          pos += Point(-2, 0)
+        }
+        b'E' => {
+          self.set(pos + Point(1, 0), b'|');
+          self.set(pos + Point(2, 0), b'.');
+          pos += Point(2, 0)
+        }
+        b'(' => fork_points.push(pos),
+        b'|' => pos = *fork_points.last().unwrap(),
+        b')' => {
+          fork_points.pop();
+        }
+        _ => unreachable!(),
+      };
+    }
+  }
+
+  fn set(&mut self, p: Point, value: u8) {
+    self.grid[p] = value;
+  }
+
+  fn get(&self, p: Point) -> u8 {
+    self.grid[p]
+  }
+
+  fn is_reachable(&self, p: Point, dir: u8) -> Option<Point> {
+    match dir {
+      b'N' if self.get(p + Point(0, -1)) == b'-' => Some(p + Point(0, -2)),
+      b'S' if self.get(p + Point(0, 1)) == b'-' => Some(p + Point(0, 2)),
+      b'W' if self.get(p + Point(-1, 0)) == b'|' => Some(p + Point(-2, 0)),
+      b'E' if self.get(p + Point(1, 0)) == b'|' => Some(p + Point(2, 0)),
+      _ => None,
+    }
+  }
+
+  fn visit_rooms(&self, pos: Point) -> HashMap<Point, u16> {
+    let mut room_door_count =
+      HashMap::<Point, u16>::with_capacity(self.grid.cells().len());
+    let mut visiting = Vec::<Point>::with_capacity(256);
+    let mut to_visit = Vec::<Point>::with_capacity(256);
+    to_visit.extend(
+      [b'N', b'E', b'W', b'S']
+        .iter()
+        .filter_map(|&dir| self.is_reachable(pos, dir))
+        .collect::<Vec<Point>>(),
+    );
+    let mut cur_dist = 0;
+    while !to_visit.is_empty() {
+      mem::swap(&mut visiting, &mut to_visit);
+      to_visit.clear();
+      cur_dist += 1;
+      while let Some(pt) = visiting.pop() {
+        // skip if already visited
+        if let Entry::Vacant(e) = room_door_count.entry(pt) {
+          e.insert(cur_dist);
+          to_visit.extend(
+            [b'N', b'E', b'W', b'S']
+              .iter()
+              .filter_map(|&dir| self.is_reachable(pt, dir))
+              .collect::<Vec<Point>>(),
+          );
+        }
+      }
+    }
+    room_door_count
+  }
+}
+
+impl Debug for Map {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.grid)
+  }
+}
+
+fn error(idx: usize) -> Box<dyn Error> {
+  // column counts `^` too
+  format!("Unexpected character in regex at column {}", idx + 2).into()
+}
+
+/** Map’s dimensions and the starting room’s position in it */
+fn compute_dims(input: &str) -> Result<(Point, Point), Box<dyn Error>> {
+  let mut fork_points = Vec::with_capacity(128);
+  let mut pos = Point(0, 0);
+  let (mut min, mut max) =
+    (Point(i32::MAX, i32::MAX), Point(-i32::MAX, -i32::MAX));
+  for (idx, c) in input.bytes().enumerate() {
+    match c {
+      b'N' => pos.1 -= 2,
+      b'E' => pos.0 += 2,
+      b'W' => pos.0 -= 2,
+      b'S' => pos.1 += 2,
+      b'(' => fork_points.push(pos),
+      b'|' => pos = *fork_points.last().ok_or_else(|| error(idx))?,
+      b')' => {
+        fork_points.pop().ok_or_else(|| error(idx))?;
+      }
+      _ => return Err(error(idx)),
+    }
+    min.0 = min.0.min(pos.0);
+    max.0 = max.0.max(pos.0);
+    min.1 = min.1.min(pos.1);
+    max.1 = max.1.max(pos.1);
+  }
+  if !fork_points.is_empty() {
+    return Err("Unbalanced `(` in regex".into());
+  }
+  // expand by 1 on all sides for the walls
+  min.0 -= 1;
+  min.1 -= 1;
+  max.0 += 1;
+  max.1 += 1;
+  // + 1 as we want stops and not spans
+  let dim_x = max.0 - min.0 + 1;
+  let dim_y = max.1 - min.1 + 1;
+  Ok((Point(dim_x, dim_y), Point(-min.0, -min.1)))
+}
+
+/// Map built from the regex and where it starts
+pub struct Input {
+  map: Map,
+  start: Point,
+}
+
+/** Parses the route regex e.g. `^ENWWW(NEEE|SSE(EE|N))$` */
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let regex = input
+    .trim()
+    .strip_prefix('^')
+    .and_then(|r| r.strip_suffix('$'))
+    .ok_or("Expected regex within `^` and `$`")?;
+  let (dims, start) = compute_dims(regex)?;
+  Ok(Input {
+    map: Map::new(dims, start, regex),
+    start,
+  })
+}
+
+/** Doors to pass through to reach the farthest room */
+pub fn part1(input: &Input) -> Answer {
+  let rooms_doors = input.map.visit_rooms(input.start);
+  let farthest_room = rooms_doors.iter().max_by(|&a, &b| a.1.cmp(b.1));
+  aoc::debug_print!("Farthest room: {:?}", farthest_room);
+  farthest_room.map_or(0, |(_, &doors)| doors).into()
+}
+
+/** Rooms needing to pass through 1000+ doors */
+pub fn part2(input: &Input) -> Answer {
+  let rooms_doors = input.map.visit_rooms(input.start);
+  rooms_doors
+    .values()
+    .filter(|&&dist| dist >= 1000)
+    .count()
+    .into()
+}
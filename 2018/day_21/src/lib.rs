@@ -0,0 +1,66 @@
+use aoc::Answer;
+use elfcode::{first_halting_value, halting_values, Halt, Image, Options};
+use std::error::Error;
+
+pub type Word = u64;
+pub type Cpu = elfcode::Cpu<Word, 6>;
+
+/// Program with its IP bound
+pub struct Input {
+  image: Image<Word, 6>,
+}
+
+impl Input {
+  /** CPU with the program loaded, set to the chosen overflow mode */
+  pub fn cpu(&self) -> Cpu {
+    self.image.cpu()
+  }
+}
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  parse_with(input, Options::default())
+}
+
+/** Program read as per `opts`; see `Image::parse` */
+pub fn parse_with(input: &str, opts: Options) -> Result<Input, Box<dyn Error>> {
+  let image = Image::parse(input, &opts)?;
+  Ok(Input { image })
+}
+
+/** Value of R0 that halts the program soonest; `None` if none does */
+pub fn first_halt(input: &Input) -> Result<Option<Halt<Word>>, Box<dyn Error>> {
+  first_halting_value(&mut input.cpu(), 0)
+}
+
+/** Values of R0 that make the program halt, earliest halting first */
+pub fn halts(input: &Input) -> Result<Vec<Halt<Word>>, Box<dyn Error>> {
+  // program halts when R0 equals a value it computes; find them all
+  halting_values(&mut input.cpu(), 0)
+}
+
+/** R0 halting the program after the fewest instructions.
+ *
+ * Panics if no value halts the program or it traps; only `Overflow::Checked`
+ * lets it trap.
+ */
+pub fn part1(input: &Input) -> Answer {
+  first_halt(input)
+    .expect("Program trapped")
+    .expect("No value of R0 halts the program")
+    .value
+    .into()
+}
+
+/** R0 halting the program after the most instructions.
+ *
+ * Panics if no value halts the program or it traps; only `Overflow::Checked`
+ * lets it trap.
+ */
+pub fn part2(input: &Input) -> Answer {
+  halts(input)
+    .expect("Program trapped")
+    .last()
+    .expect("No value of R0 halts the program")
+    .value
+    .into()
+}
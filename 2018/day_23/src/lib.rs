@@ -0,0 +1,291 @@
+use aoc::Answer;
+use std::{
+  cmp::{Ordering, Reverse},
+  collections::BinaryHeap,
+  error::Error,
+  fmt::{self, Display, Formatter},
+  ops::{Add, Sub},
+  str::FromStr,
+};
+
+#[derive(Debug, Copy, Clone)]
+struct Point(f64, f64, f64);
+
+impl Sub for Point {
+  type Output = Self;
+
+  fn sub(self, other: Self) -> Self::Output {
+    Point(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+  }
+}
+
+// Pair-wise addition using operator+
+impl Add for Point {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self::Output {
+    Point(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+  }
+}
+
+impl Point {
+  fn add(self, d: f64) -> Self {
+    Point(self.0 + d, self.1 + d, self.2 + d)
+  }
+
+  fn abs(self) -> Self {
+    Point(self.0.abs(), self.1.abs(), self.2.abs())
+  }
+
+  fn max(self) -> f64 {
+    if self.0 >= self.1 && self.0 >= self.2 {
+      self.0
+    } else if self.1 >= self.0 && self.1 >= self.2 {
+      self.1
+    } else {
+      self.2
+    }
+  }
+
+  // Component-wise addition; sum of all components
+  fn sum(self) -> f64 {
+    self.0 + self.1 + self.2
+  }
+
+  fn round(self) -> Self {
+    Point(self.0.round(), self.1.round(), self.2.round())
+  }
+}
+
+impl PartialEq for Point {
+  fn eq(&self, other: &Self) -> bool {
+    ((self.0 - other.0).abs() < 0.5)
+      && ((self.1 - other.1).abs() < 0.5)
+      && ((self.2 - other.2).abs() < 0.5)
+  }
+}
+impl Eq for Point {}
+impl Ord for Point {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (self.0.to_bits(), self.1.to_bits(), self.2.to_bits()).cmp(&(
+      other.0.to_bits(),
+      other.1.to_bits(),
+      other.2.to_bits(),
+    ))
+  }
+}
+impl PartialOrd for Point {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Display for Point {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "({}, {}, {})", self.0, self.1, self.2)
+  }
+}
+
+/// Nanobot’s position and signal radius
+#[derive(Debug, Clone)]
+pub struct Bot {
+  pos: Point,
+  radius: f64,
+}
+
+impl Bot {
+  fn is_point_in_range(&self, p: Point) -> bool {
+    // Manhattan/Taxicab (L1) distance
+    (self.pos - p).abs().sum() <= self.radius
+  }
+}
+
+impl FromStr for Bot {
+  type Err = Box<dyn Error>;
+
+  /** Parses `pos=<0,0,0>, r=4` */
+  fn from_str(input: &str) -> Result<Bot, Self::Err> {
+    let invalid = || format!("Invalid nanobot `{}`", input);
+    let (pos, radius) = input
+      .strip_prefix("pos=<")
+      .and_then(|s| s.split_once(">, r="))
+      .ok_or_else(invalid)?;
+    let coords = pos
+      .split(',')
+      .map(|c| c.trim().parse::<i32>())
+      .collect::<Result<Vec<_>, _>>()?;
+    let [x, y, z] = coords[..] else {
+      return Err(invalid().into());
+    };
+    let pos = Point(x.into(), y.into(), z.into());
+    let radius = radius.trim().parse::<u32>()?.into();
+    Ok(Bot { pos, radius })
+  }
+}
+
+// Using half size for an octant will lead to off-by-one errors; either |centre|
+// and/or |half_size| should be floating point.
+//
+//           |  |  |
+//          -1  0  1
+//
+// Center 0, half size 1 AABB can’t be split with just integers.
+
+struct Aabb {
+  centre: Point,
+  half_size: f64,
+}
+
+impl PartialEq for Aabb {
+  fn eq(&self, other: &Self) -> bool {
+    self.to_bits().eq(&other.to_bits())
+  }
+}
+impl Eq for Aabb {}
+impl Ord for Aabb {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.to_bits().cmp(&other.to_bits())
+  }
+}
+impl PartialOrd for Aabb {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Aabb {
+  fn to_bits(&self) -> [u64; 4] {
+    [
+      self.half_size.to_bits(),
+      self.centre.0.to_bits(),
+      self.centre.1.to_bits(),
+      self.centre.2.to_bits(),
+    ]
+  }
+
+  fn new(half_size: f64) -> Self {
+    Aabb {
+      centre: Point(0.0, 0.0, 0.0),
+      half_size,
+    }
+  }
+
+  fn octants(&self) -> [Self; 8] {
+    let mut hs = self.half_size / 2.0;
+    if hs.fract() < 0.5 {
+      hs = hs.round();
+    }
+    [
+      Aabb {
+        centre: self.centre + Point(hs, hs, hs),
+        half_size: hs,
+      },
+      Aabb {
+        centre: self.centre + Point(-hs, hs, hs),
+        half_size: hs,
+      },
+      Aabb {
+        centre: self.centre + Point(-hs, -hs, hs),
+        half_size: hs,
+      },
+      Aabb {
+        centre: self.centre + Point(hs, -hs, hs),
+        half_size: hs,
+      },
+      Aabb {
+        centre: self.centre + Point(hs, hs, -hs),
+        half_size: hs,
+      },
+      Aabb {
+        centre: self.centre + Point(-hs, hs, -hs),
+        half_size: hs,
+      },
+      Aabb {
+        centre: self.centre + Point(-hs, -hs, -hs),
+        half_size: hs,
+      },
+      Aabb {
+        centre: self.centre + Point(hs, -hs, -hs),
+        half_size: hs,
+      },
+    ]
+  }
+
+  fn has_bot_range(&self, b: &Bot) -> bool {
+    let low = self.centre.add(-self.half_size);
+    let high = self.centre.add(self.half_size);
+    let dist = (b.pos - low).abs().sum() + (b.pos - high).abs().sum();
+    (dist * 0.5) <= ((3.0 * self.half_size) + b.radius)
+
+    // dist(centre.x, pos.x) <= half_size + (radius / 3)
+    // dist(centre.y, pos.y) <= half_size + (radius / 3)
+    // dist(centre.z, pos.z) <= half_size + (radius / 3)
+
+    // in 2D: l1(c, p) <= 2 * (half_size + (b.radius / 2))
+    // (self.centre - b.pos).abs().sum() <= ((3.0 * self.half_size) + b.radius)
+  }
+
+  fn distance_from_origin(&self) -> f64 {
+    self.centre.abs().sum()
+  }
+}
+
+pub type Input = Vec<Bot>;
+
+pub fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+  let bots: Vec<Bot> = aoc::parse_lines(input)?;
+  match bots.is_empty() {
+    true => Err("No nanobots".into()),
+    false => Ok(bots),
+  }
+}
+
+/** Nanobots in range of the one with the strongest signal */
+pub fn part1(bots: &Input) -> Answer {
+  let max_bot =
+    bots.iter().fold(
+      &bots[0],
+      |max, b| if b.radius > max.radius { b } else { max },
+    );
+  bots
+    .iter()
+    .filter(|&b| max_bot.is_point_in_range(b.pos))
+    .count()
+    .into()
+}
+
+/** Distance from origin to the point in range of most nanobots; of many such
+ * points, the closest */
+pub fn part2(bots: &Input) -> Answer {
+  let max_coord = bots
+    .iter()
+    .map(|b| b.pos.abs().add(b.radius).max())
+    .fold(f64::MIN, f64::max);
+  let cave = Aabb::new(max_coord);
+  // Minimize size of AABB, distance to origin.  Reverse field 2, 3 on max-heap.
+  let mut heap = BinaryHeap::new();
+  heap.push((
+    bots.len(),
+    Reverse(cave.half_size.to_bits()),
+    Reverse(cave.distance_from_origin().to_bits()),
+    cave,
+  ));
+  let mut i = 0;
+  while let Some((bc, _, _, aabb)) = heap.pop() {
+    i += 1;
+    if aabb.half_size < 1.0 {
+      aoc::debug_print!("Iteration count: {}, Bot count: {}", i, bc);
+      return (aabb.centre.round().abs().sum() as i64).into();
+    }
+    for oct in aabb.octants() {
+      let bot_count = bots.iter().filter(|&bot| oct.has_bot_range(bot)).count();
+      heap.push((
+        bot_count,
+        Reverse(oct.half_size.to_bits()),
+        Reverse(oct.distance_from_origin().to_bits()),
+        oct,
+      ));
+    }
+  }
+  unreachable!("Octants are split till they're smaller than a unit")
+}
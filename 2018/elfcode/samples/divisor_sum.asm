@@ -0,0 +1,31 @@
+; Sums divisors of N into `sum` the way day 19 does, with the loop at a
+; different offset and register allocation; `elfasm < samples/divisor_sum.asm`
+.ip r2
+.reg sum r5
+.reg outer r0
+.reg inner r3
+.reg tmp r1
+.reg n r4
+.const N 945
+
+        seti N 0 n
+        seti 0 0 sum
+        jmp start
+        halt                    ; padding to move the loop off line 3
+start:  seti 1 0 outer
+outer_loop:
+        seti 1 0 inner
+inner_loop:
+        mulr outer inner tmp
+        eqrr tmp n tmp
+        addr tmp ip ip
+        addi ip 1 ip
+        addr outer sum sum
+        addi inner 1 inner
+        gtrr inner n tmp
+        addr ip tmp ip
+        seti inner_loop-1 0 ip
+        addi outer 1 outer
+        gtrr outer n tmp
+        jf tmp outer_loop
+        halt
@@ -0,0 +1,233 @@
+use crate::{Instruction, Op, Program, Word};
+use std::{collections::HashMap, error::Error};
+
+/// Operand as written in the source; resolved once all labels are known.
+enum Arg<'a> {
+  Text(&'a str),
+  /// Immediate making the IP jump to a label
+  Jump(&'a str),
+  /// Immediate making the IP jump past the last instruction
+  Halt,
+}
+
+struct Item<'a> {
+  line: usize,
+  op: Op,
+  args: [Arg<'a>; 3],
+}
+
+#[derive(Default)]
+struct Assembler<'a> {
+  ip_reg: Option<usize>,
+  regs: HashMap<&'a str, usize>,
+  /// Value as written and its line; resolved once labels are known
+  consts: HashMap<&'a str, (&'a str, usize)>,
+  labels: HashMap<&'a str, usize>,
+  items: Vec<Item<'a>>,
+}
+
+fn error(line: usize, msg: impl AsRef<str>) -> Box<dyn Error> {
+  format!("{} at line {}", msg.as_ref(), line).into()
+}
+
+fn is_name(s: &str) -> bool {
+  s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+    && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl<'a> Assembler<'a> {
+  fn register(
+    &self,
+    token: &str,
+    line: usize,
+  ) -> Result<usize, Box<dyn Error>> {
+    if token == "ip" {
+      return self
+        .ip_reg
+        .ok_or_else(|| error(line, "`ip` used before `.ip`"));
+    }
+    if let Some(&r) = self.regs.get(token) {
+      return Ok(r);
+    }
+    token
+      .strip_prefix('r')
+      .unwrap_or(token)
+      .parse()
+      .map_err(|_| error(line, format!("Unknown register `{}`", token)))
+  }
+
+  /** Value of `name`, `name+k`, `name-k` or a number */
+  fn immediate(&self, token: &str, line: usize) -> Result<u64, Box<dyn Error>> {
+    self.resolve(token, line, 0)
+  }
+
+  /** `immediate` with `depth` constants followed to get here */
+  fn resolve(
+    &self,
+    token: &str,
+    line: usize,
+    depth: usize,
+  ) -> Result<u64, Box<dyn Error>> {
+    if let Ok(v) = token.parse() {
+      return Ok(v);
+    }
+    let (name, offset) = match token.find(['+', '-']) {
+      Some(idx) => {
+        let offset = token[idx + 1..]
+          .parse::<i64>()
+          .map_err(|_| error(line, format!("Invalid offset in `{}`", token)))?;
+        match &token[idx..=idx] {
+          "-" => (&token[..idx], -offset),
+          _ => (&token[..idx], offset),
+        }
+      }
+      None => (token, 0),
+    };
+    let base = match (self.consts.get(name), self.labels.get(name)) {
+      // a chain longer than all constants goes round in a circle
+      (Some(_), _) if depth >= self.consts.len() => {
+        return Err(error(
+          line,
+          format!("Circular constant definitions through `{}`", name),
+        ))
+      }
+      (Some(&(value, def)), _) => self.resolve(value, def, depth + 1)? as i64,
+      (None, Some(&addr)) => addr as i64,
+      (None, None) => {
+        return Err(error(line, format!("Unknown symbol `{}`", name)))
+      }
+    };
+    u64::try_from(base + offset)
+      .map_err(|_| error(line, format!("`{}` is negative", token)))
+  }
+
+  fn push(&mut self, line: usize, op: Op, args: [Arg<'a>; 3]) {
+    self.items.push(Item { line, op, args });
+  }
+
+  /** First pass: note directives and labels, expand pseudo-instructions */
+  fn scan(&mut self, source: &'a str) -> Result<(), Box<dyn Error>> {
+    for (idx, text) in source.lines().enumerate() {
+      let line = idx + 1;
+      let mut code = text.split(';').next().unwrap_or("").trim();
+      // labels may precede an instruction on the same line
+      while let Some(colon) = code.find(':') {
+        let label = code[..colon].trim();
+        if !is_name(label) {
+          return Err(error(line, format!("Invalid label `{}`", label)));
+        }
+        if self.labels.insert(label, self.items.len()).is_some() {
+          return Err(error(line, format!("Duplicate label `{}`", label)));
+        }
+        code = code[colon + 1..].trim();
+      }
+      let tokens = code.split_ascii_whitespace().collect::<Vec<_>>();
+      let ip = || Arg::Text("ip");
+      let zero = || Arg::Text("0");
+      match tokens.as_slice() {
+        [] => (),
+        [".ip", reg] | ["#ip", reg] => {
+          self.ip_reg = Some(self.register(reg, line)?);
+        }
+        [".reg", name, reg] if is_name(name) => {
+          let r = self.register(reg, line)?;
+          self.regs.insert(name, r);
+        }
+        // may refer to labels and constants defined further on
+        [".const", name, value] if is_name(name) => {
+          if self.consts.insert(name, (value, line)).is_some() {
+            return Err(error(line, format!("Duplicate constant `{}`", name)));
+          }
+        }
+        ["jmp", label] => {
+          self.push(line, Op::Seti, [Arg::Jump(label), zero(), ip()])
+        }
+        // rF is 1: skip `ip += 1` and jump; rF is 0: skip the jump
+        ["jt", flag, label] => {
+          self.push(line, Op::Addr, [Arg::Text(flag), ip(), ip()]);
+          self.push(line, Op::Addi, [ip(), Arg::Text("1"), ip()]);
+          self.push(line, Op::Seti, [Arg::Jump(label), zero(), ip()]);
+        }
+        // rF is 0: jump; rF is 1: skip the jump
+        ["jf", flag, label] => {
+          self.push(line, Op::Addr, [Arg::Text(flag), ip(), ip()]);
+          self.push(line, Op::Seti, [Arg::Jump(label), zero(), ip()]);
+        }
+        ["halt"] => self.push(line, Op::Seti, [Arg::Halt, zero(), ip()]),
+        [op, a, b, c] => {
+          let op = op.parse::<Op>().map_err(|e| error(line, e.to_string()))?;
+          self.push(line, op, [Arg::Text(a), Arg::Text(b), Arg::Text(c)]);
+        }
+        _ => return Err(error(line, format!("Invalid statement `{}`", code))),
+      }
+    }
+    Ok(())
+  }
+
+  /** Second pass: resolve operands now that label addresses are known */
+  fn emit<W: Word>(&self) -> Result<Program<W>, Box<dyn Error>> {
+    let len = self.items.len() as u64;
+    let instructions = self
+      .items
+      .iter()
+      .map(|item| {
+        let line = item.line;
+        let mut values = [W::ZERO; 3];
+        let is_reg = [item.op.reads_a(), item.op.reads_b(), true];
+        for ((v, arg), is_reg) in values.iter_mut().zip(&item.args).zip(is_reg)
+        {
+          let value = match (arg, is_reg) {
+            (Arg::Text(t), true) => self.register(t, line)? as u64,
+            (Arg::Text(t), false) => self.immediate(t, line)?,
+            // IP is incremented after the jump instruction
+            (Arg::Jump(label), _) => self
+              .immediate(label, line)?
+              .checked_sub(1)
+              .ok_or_else(|| error(line, "Can't jump to the first line"))?,
+            (Arg::Halt, _) => len - 1,
+          };
+          *v = W::from_usize(value as usize);
+          if v.to_usize() as u64 != value {
+            return Err(error(
+              line,
+              format!("Value {} doesn't fit in a word", value),
+            ));
+          }
+        }
+        Ok(Instruction::new(item.op, values[0], values[1], values[2]))
+      })
+      .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    Ok(Program {
+      ip_reg: self.ip_reg,
+      instructions,
+    })
+  }
+}
+
+/// Assembles elfcode source with labels, register aliases and constants.
+///
+/// ```text
+/// .ip r4                ; bind IP; also `#ip 4`
+/// .reg sum r0           ; alias a register
+/// .const N 10           ; name a constant
+/// loop: addi sum 1 sum  ; labels name instruction addresses
+///       gtri sum N r1
+///       jf r1 loop      ; jump if r1 is 0; `jt` jumps if it is 1
+///       jmp done        ; unconditional jump
+/// done: halt            ; jump past the last instruction
+/// ```
+/// Immediates may be numbers, constants or labels with an optional `+k` or
+/// `-k` offset, and must fit in a word `W`; constants may use labels and
+/// constants defined after them.  Registers may be `rN`, `N`, an alias or
+/// `ip`.
+pub fn assemble<W: Word>(source: &str) -> Result<Program<W>, Box<dyn Error>> {
+  let mut asm = Assembler::default();
+  asm.scan(source)?;
+  // report bad constants even if unused
+  let mut consts = asm.consts.values().collect::<Vec<_>>();
+  consts.sort_unstable_by_key(|&&(_, line)| line);
+  for &(value, line) in consts {
+    asm.immediate(value, line)?;
+  }
+  asm.emit()
+}
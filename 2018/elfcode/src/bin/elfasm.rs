@@ -0,0 +1,25 @@
+use elfcode::{assemble, OpcodeMap, Program};
+use std::{
+  env,
+  error::Error,
+  fs,
+  io::{self, Read},
+};
+
+/** Assemble elfcode source read from stdin into text the solvers read; with
+ * `--numeric`, into opcodes mapped as per `--opcodes` file or the default */
+fn main() -> Result<(), Box<dyn Error>> {
+  let mut source = String::new();
+  io::stdin().read_to_string(&mut source)?;
+  let program: Program<u64> = assemble(&source)?;
+  if !env::args().any(|arg| arg == "--numeric") {
+    print!("{}", program);
+    return Ok(());
+  }
+  let opcodes = match env::args().skip_while(|arg| arg != "--opcodes").nth(1) {
+    Some(path) => fs::read_to_string(path)?.parse()?,
+    None => OpcodeMap::default(),
+  };
+  print!("{}", opcodes.encode(&program));
+  Ok(())
+}
@@ -0,0 +1,26 @@
+use elfcode::{Cpu, Debugger, Program};
+use std::{
+  env,
+  error::Error,
+  fs,
+  io::{self, Write},
+};
+
+/** Debug elfcode program at path given as argument; commands from stdin.
+ * `--overflow wrapping|saturating|checked` sets the arithmetic mode. */
+fn main() -> Result<(), Box<dyn Error>> {
+  let path = env::args()
+    .nth(1)
+    .ok_or("Usage: elfdbg PROGRAM [--overflow MODE]; `h` at prompt for help")?;
+  let program = fs::read_to_string(path)?.parse::<Program<u64>>()?;
+  let mut cpu = Cpu::<u64, 6>::new();
+  cpu.load(program)?;
+  if let Some(mode) = env::args().skip_while(|arg| arg != "--overflow").nth(1) {
+    cpu.set_overflow(mode.parse()?);
+  }
+  let mut debugger = Debugger::new(cpu);
+  let mut out = io::stdout().lock();
+  debugger.repl(io::stdin().lock(), &mut out)?;
+  writeln!(out)?;
+  Ok(())
+}
@@ -0,0 +1,14 @@
+use elfcode::{disassemble, Program};
+use std::{
+  error::Error,
+  io::{self, Read},
+};
+
+/** Disassemble elfcode read from stdin into pseudo-code */
+fn main() -> Result<(), Box<dyn Error>> {
+  let mut input = String::new();
+  io::stdin().read_to_string(&mut input)?;
+  let program = input.parse::<Program<u64>>()?;
+  print!("{}", disassemble(&program));
+  Ok(())
+}
@@ -0,0 +1,57 @@
+use elfcode::{explore, Cpu, Limits, Outcome, Program};
+use std::{env, error::Error, fs};
+
+// constraints listed per path
+const SHOWN: usize = 8;
+
+/** Value following `flag` on the command line, parsed */
+fn option<T: std::str::FromStr>(flag: &str) -> Result<Option<T>, String> {
+  match env::args().skip_while(|arg| arg != flag).nth(1) {
+    Some(v) => v
+      .parse()
+      .map(Some)
+      .map_err(|_| format!("Invalid value for {}: {}", flag, v)),
+    None => Ok(None),
+  }
+}
+
+/** Explore paths of elfcode program at path given as argument with an input
+ * register unknown; lists constraints on it per path, halting soonest first.
+ * Options: `--input R` (default 0), `--steps N` and `--paths N` limits. */
+fn main() -> Result<(), Box<dyn Error>> {
+  let path = env::args()
+    .nth(1)
+    .ok_or("Usage: elfsym PROGRAM [--input R] [--steps N] [--paths N]")?;
+  let program = fs::read_to_string(path)?.parse::<Program<u64>>()?;
+  let mut cpu = Cpu::<u64, 6>::new();
+  cpu.load(program)?;
+  let input = option("--input")?.unwrap_or(0);
+  let mut limits = Limits::default();
+  if let Some(steps) = option("--steps")? {
+    limits.steps = steps;
+  }
+  if let Some(paths) = option("--paths")? {
+    limits.paths = paths;
+  }
+
+  let mut paths = explore(&cpu, input, limits);
+  paths.sort_by_key(|p| (p.outcome != Outcome::Halted, p.steps));
+  for p in &paths {
+    match &p.outcome {
+      Outcome::Halted => print!("halts after {} steps", p.steps),
+      Outcome::Loops => print!("loops after {} steps", p.steps),
+      Outcome::Trapped => print!("traps after {} steps", p.steps),
+      Outcome::Unknown(why) => print!("{} after {} steps", why, p.steps),
+    }
+    let shown = p.constraints.iter().take(SHOWN).map(|c| c.to_string());
+    let mut conds = shown.collect::<Vec<_>>().join(" and ");
+    if p.constraints.len() > SHOWN {
+      conds += &format!(" and {} more", p.constraints.len() - SHOWN);
+    }
+    match conds.is_empty() {
+      true => println!(),
+      false => println!(" if {}", conds),
+    }
+  }
+  Ok(())
+}
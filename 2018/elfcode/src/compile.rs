@@ -0,0 +1,226 @@
+use crate::{Cpu, Instruction, Op, Overflow, Trap, Word};
+use std::error::Error;
+
+/// Operand with IP register reads and immediates resolved at compile time.
+#[derive(Copy, Clone)]
+enum Src<W> {
+  Reg(usize),
+  Const(W),
+}
+
+// returns false, leaving registers untouched, if the instruction traps
+type Micro<W, const N: usize> = Box<dyn Fn(&mut [W; N]) -> bool>;
+
+// Build a closure specialised for the operand kinds; constant operands are
+// captured, and when both are constant the result itself is.  `$e` gives
+// `None` on overflow.
+macro_rules! specialise {
+  ($a:expr, $b:expr, $c:expr, |$x:ident, $y:ident| $e:expr) => {{
+    let c = $c;
+    let store = move |r: &mut [W; N], v: Option<W>| match v {
+      Some(v) => {
+        r[c] = v;
+        true
+      }
+      None => false,
+    };
+    match ($a, $b) {
+      (Src::Const($x), Src::Const($y)) => {
+        let v = $e;
+        Box::new(move |r: &mut [W; N]| store(r, v)) as Micro<W, N>
+      }
+      (Src::Reg(i), Src::Const($y)) => Box::new(move |r: &mut [W; N]| {
+        let $x = r[i];
+        store(r, $e)
+      }),
+      (Src::Const($x), Src::Reg(j)) => Box::new(move |r: &mut [W; N]| {
+        let $y = r[j];
+        store(r, $e)
+      }),
+      (Src::Reg(i), Src::Reg(j)) => Box::new(move |r: &mut [W; N]| {
+        let ($x, $y) = (r[i], r[j]);
+        store(r, $e)
+      }),
+    }
+  }};
+}
+
+#[derive(Copy, Clone)]
+enum Exit {
+  /// Block ends without writing the IP; continue at this line
+  Fall(usize),
+  /// Block ends with a write to the IP register
+  Jump,
+}
+
+struct Block<W, const N: usize> {
+  ops: Vec<Micro<W, N>>,
+  // line of each op
+  lines: Vec<usize>,
+  // instructions the block runs before each op, counting constant jumps
+  // compiled away
+  before: Vec<u64>,
+  // instructions the block stands for
+  count: u64,
+  exit: Exit,
+}
+
+/// Program compiled ahead of time into basic blocks of closures.
+///
+/// Since any line might be a computed jump’s target, a block is built starting
+/// at every line; it runs till an instruction writes a non-constant value to
+/// the IP, the program ends or a _stop_ line is reached.  Constant jumps are
+/// followed at compile time.  Stops let callers inspect the CPU at lines of
+/// interest between `run` calls.  Code is compiled for the CPU’s overflow
+/// mode; instructions that trap are rerun on the CPU to report the trap.
+pub struct Compiled<W, const N: usize> {
+  blocks: Vec<Block<W, N>>,
+  stops: Vec<bool>,
+  ip_reg: Option<usize>,
+}
+
+impl<W: Word + 'static, const N: usize> Compiled<W, N> {
+  // bounds blocks following constant jumps e.g. a loop with no exit
+  const BLOCK_MAX: u64 = 256;
+
+  /** Compile the program loaded in `cpu` */
+  pub fn new(cpu: &Cpu<W, N>, stops: &[usize]) -> Result<Self, Box<dyn Error>> {
+    let (code, ip_reg, overflow) =
+      (cpu.program(), cpu.ip_reg(), cpu.overflow());
+    if let Some(r) = ip_reg.filter(|&r| r >= N) {
+      return Err(format!("IP bound to invalid register {}", r).into());
+    }
+    let mut is_stop = vec![false; code.len()];
+    stops
+      .iter()
+      .filter(|&&s| s < code.len())
+      .for_each(|&s| is_stop[s] = true);
+    let mut blocks = Vec::with_capacity(code.len());
+    for start in 0..code.len() {
+      let mut ops = Vec::new();
+      let mut lines = Vec::new();
+      let mut before = Vec::new();
+      let mut count = 0;
+      let mut line = start;
+      let exit = loop {
+        let i = match code.get(line) {
+          Some(i) if line == start || !is_stop[line] => i,
+          _ => break Exit::Fall(line),
+        };
+        let (op, folded) = Self::micro(i, line, ip_reg, overflow);
+        count += 1;
+        line = match (Some(i.c.to_usize()) == ip_reg, folded) {
+          (false, _) => {
+            ops.push(op);
+            lines.push(line);
+            before.push(count - 1);
+            line + 1
+          }
+          // jump to a constant target; carry on compiling there and skip the
+          // IP register write as exits update it
+          (true, Some(v)) if count < Self::BLOCK_MAX => {
+            v.to_usize().saturating_add(1)
+          }
+          (true, _) => {
+            ops.push(op);
+            lines.push(line);
+            before.push(count - 1);
+            break Exit::Jump;
+          }
+        };
+      };
+      blocks.push(Block {
+        ops,
+        lines,
+        before,
+        count,
+        exit,
+      });
+    }
+    Ok(Compiled {
+      blocks,
+      stops: is_stop,
+      ip_reg,
+    })
+  }
+
+  /** Closure for `i` at `line`; with the value it writes if that’s constant
+   */
+  fn micro(
+    i: &Instruction<W>,
+    line: usize,
+    ip_reg: Option<usize>,
+    overflow: Overflow,
+  ) -> (Micro<W, N>, Option<W>) {
+    let src = |x: W, is_reg: bool| match (is_reg, x.to_usize()) {
+      (true, r) if Some(r) == ip_reg => Some(Src::Const(W::from_usize(line))),
+      (true, r) if r < N => Some(Src::Reg(r)),
+      (true, _) => None,
+      (false, _) => Some(Src::Const(x)),
+    };
+    let (a, b, c) = match (
+      src(i.a, i.op.reads_a()),
+      src(i.b, i.op.reads_b()),
+      i.c.to_usize(),
+    ) {
+      (Some(a), Some(b), c) if c < N => (a, b, c),
+      // invalid register; always traps
+      _ => return (Box::new(|_: &mut [W; N]| false), None),
+    };
+    let folded = match (a, b) {
+      (Src::Const(x), Src::Const(y)) => i.op.eval(x, y, overflow),
+      _ => None,
+    };
+    let op = match i.op {
+      Op::Addr | Op::Addi => specialise!(a, b, c, |x, y| overflow.add(x, y)),
+      Op::Mulr | Op::Muli => specialise!(a, b, c, |x, y| overflow.mul(x, y)),
+      Op::Banr | Op::Bani => specialise!(a, b, c, |x, y| Some(x & y)),
+      Op::Borr | Op::Bori => specialise!(a, b, c, |x, y| Some(x | y)),
+      Op::Setr | Op::Seti => specialise!(a, b, c, |x, _y| Some(x)),
+      Op::Gtir | Op::Gtri | Op::Gtrr => {
+        specialise!(a, b, c, |x, y| Some(W::from_bool(x > y)))
+      }
+      Op::Eqir | Op::Eqri | Op::Eqrr => {
+        specialise!(a, b, c, |x, y| Some(W::from_bool(x == y)))
+      }
+    };
+    (op, folded)
+  }
+
+  /** Run from `cpu`’s IP till the program halts or a stop line is reached;
+   * returns count of instructions executed.  At least one block runs, so
+   * calling again at a stop resumes execution.  On a trap the CPU is left at
+   * the trapping instruction as `Cpu::step` leaves it. */
+  pub fn run(&self, cpu: &mut Cpu<W, N>) -> Result<u64, Trap<W>> {
+    let mut ip = cpu.ip();
+    let mut count = 0;
+    while let Some(block) = self.blocks.get(ip) {
+      if let Some(k) = block.ops.iter().position(|op| !op(&mut cpu.reg)) {
+        // interpreter reports the trap with the IP register set as usual; it
+        // executes the instruction after all if the CPU's mode has changed
+        count += block.before[k];
+        cpu.set_ip(block.lines[k]);
+        cpu.step()?;
+        count += 1;
+        ip = cpu.ip();
+        continue;
+      }
+      count += block.count;
+      ip = match (block.exit, self.ip_reg) {
+        (Exit::Fall(next), Some(r)) => {
+          // IP as written before the last instruction, or by a constant jump
+          cpu.reg[r] = W::from_usize(next - 1);
+          next
+        }
+        (Exit::Fall(next), None) => next,
+        (Exit::Jump, Some(r)) => cpu.reg[r].to_usize().saturating_add(1),
+        (Exit::Jump, None) => unreachable!(),
+      };
+      if self.stops.get(ip) == Some(&true) {
+        break;
+      }
+    }
+    cpu.set_ip(ip);
+    Ok(count)
+  }
+}
@@ -0,0 +1,326 @@
+use crate::{
+  History, Instruction, Op, Overflow, Profile, Program, Snapshot, Trap,
+  TrapKind, Word,
+};
+use std::error::Error;
+
+/// Elfcode CPU with `N` registers of type `W`.
+///
+/// When the instruction pointer is bound to a register (`#ip N`), the IP is
+/// copied to that register before each instruction and read back from it after;
+/// writes to the register are jumps.  Execution halts when the IP leaves the
+/// loaded program.  An instruction that can’t execute, by overflowing in
+/// `Overflow::Checked` mode or naming a register past `N`, traps.
+pub struct Cpu<W, const N: usize> {
+  pub reg: [W; N],
+  ip: usize,
+  ip_reg: Option<usize>,
+  overflow: Overflow,
+  program: Vec<Instruction<W>>,
+  profile: Option<Profile>,
+  history: Option<History<W, N>>,
+}
+
+type Operation<W, const N: usize> =
+  fn(&mut Cpu<W, N>, W, W, W) -> Result<(), TrapKind>;
+
+impl<W: Word, const N: usize> Default for Cpu<W, N> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<W: Word, const N: usize> Cpu<W, N> {
+  pub const REG_COUNT: usize = N;
+
+  // operations jump table; indexed by `Op as usize`
+  const OPS: [Operation<W, N>; Op::COUNT] = [
+    Self::addr,
+    Self::addi,
+    Self::mulr,
+    Self::muli,
+    Self::banr,
+    Self::bani,
+    Self::borr,
+    Self::bori,
+    Self::setr,
+    Self::seti,
+    Self::gtir,
+    Self::gtri,
+    Self::gtrr,
+    Self::eqir,
+    Self::eqri,
+    Self::eqrr,
+  ];
+
+  /** Create a CPU with no program loaded and IP unbound */
+  pub fn new() -> Self {
+    Cpu {
+      reg: [W::ZERO; N],
+      ip: 0,
+      ip_reg: None,
+      overflow: Overflow::default(),
+      program: Vec::new(),
+      profile: None,
+      history: None,
+    }
+  }
+
+  /** Load `program` and bind IP as it specifies; resets the IP */
+  pub fn load(&mut self, program: Program<W>) -> Result<(), Box<dyn Error>> {
+    self.bind_ip(program.ip_reg)?;
+    self.program = program.instructions;
+    self.ip = 0;
+    if self.profile.is_some() {
+      self.enable_profiling();
+    }
+    if let Some(h) = &mut self.history {
+      h.clear();
+    }
+    Ok(())
+  }
+
+  /** Count hits per line and back-edges on `step`s from here on */
+  pub fn enable_profiling(&mut self) {
+    self.profile = Some(Profile::new(self.program.len()));
+  }
+
+  pub fn profile(&self) -> Option<&Profile> {
+    self.profile.as_ref()
+  }
+
+  /** Stop profiling and return what’s collected */
+  pub fn take_profile(&mut self) -> Option<Profile> {
+    self.profile.take()
+  }
+
+  /** Log the state before each of the last `capacity` `step`s from here on,
+   * for `step_back` */
+  pub fn enable_history(&mut self, capacity: usize) {
+    self.history = Some(History::new(capacity));
+  }
+
+  pub fn history(&self) -> Option<&History<W, N>> {
+    self.history.as_ref()
+  }
+
+  pub fn snapshot(&self) -> Snapshot<W, N> {
+    Snapshot {
+      reg: self.reg,
+      ip: self.ip,
+      ip_reg: self.ip_reg,
+      overflow: self.overflow,
+    }
+  }
+
+  /** Return to `snapshot`; forgets history as it no longer leads here */
+  pub fn restore(&mut self, snapshot: &Snapshot<W, N>) {
+    self.set_state(snapshot);
+    if let Some(h) = &mut self.history {
+      h.clear();
+    }
+  }
+
+  fn set_state(&mut self, state: &Snapshot<W, N>) {
+    self.reg = state.reg;
+    self.ip = state.ip;
+    self.ip_reg = state.ip_reg;
+    self.overflow = state.overflow;
+  }
+
+  /** Undo the latest logged `step`; returns false if none is left */
+  pub fn step_back(&mut self) -> bool {
+    match self.history.as_mut().and_then(History::pop) {
+      Some(state) => {
+        self.set_state(&state);
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn bind_ip(
+    &mut self,
+    ip_reg: Option<usize>,
+  ) -> Result<(), Box<dyn Error>> {
+    match ip_reg {
+      Some(r) if r >= N => Err(Box::<dyn Error>::from(format!(
+        "Invalid input: specify a register in [0, {}) range.",
+        N
+      ))),
+      _ => {
+        self.ip_reg = ip_reg;
+        Ok(())
+      }
+    }
+  }
+
+  pub fn overflow(&self) -> Overflow {
+    self.overflow
+  }
+
+  pub fn set_overflow(&mut self, overflow: Overflow) {
+    self.overflow = overflow;
+  }
+
+  pub fn ip_reg(&self) -> Option<usize> {
+    self.ip_reg
+  }
+
+  pub fn ip(&self) -> usize {
+    self.ip
+  }
+
+  pub fn set_ip(&mut self, ip: usize) {
+    self.ip = ip;
+  }
+
+  pub fn program(&self) -> &[Instruction<W>] {
+    &self.program
+  }
+
+  /** Instruction at IP; `None` if CPU is halted */
+  pub fn current(&self) -> Option<&Instruction<W>> {
+    self.program.get(self.ip)
+  }
+
+  pub fn is_halted(&self) -> bool {
+    self.ip >= self.program.len()
+  }
+
+  /** Reset registers and IP; forgets history */
+  pub fn clear(&mut self) {
+    self.reg.fill(W::ZERO);
+    self.ip = 0;
+    if let Some(h) = &mut self.history {
+      h.clear();
+    }
+  }
+
+  /** Execute `i` on the registers without touching the IP; registers are
+   * unchanged if it traps */
+  pub fn execute(&mut self, i: &Instruction<W>) -> Result<(), TrapKind> {
+    (Self::OPS[i.op as usize])(self, i.a, i.b, i.c)
+  }
+
+  /** Execute instruction at IP; returns false if CPU is halted.  On a trap
+   * the IP stays at the instruction. */
+  pub fn step(&mut self) -> Result<bool, Trap<W>> {
+    let from = self.ip;
+    let i = match self.program.get(from) {
+      Some(&i) => i,
+      None => return Ok(false),
+    };
+    let before = self.history.is_some().then(|| self.snapshot());
+    if let Some(r) = self.ip_reg {
+      self.reg[r] = W::from_usize(from);
+    }
+    self.execute(&i).map_err(|kind| Trap {
+      kind,
+      ip: from,
+      instruction: i,
+    })?;
+    if let Some(r) = self.ip_reg {
+      self.ip = self.reg[r].to_usize();
+    }
+    self.ip = self.ip.saturating_add(1);
+    if let Some(p) = &mut self.profile {
+      p.record(from, self.ip);
+    }
+    if let (Some(h), Some(state)) = (&mut self.history, before) {
+      h.push(state);
+    }
+    Ok(true)
+  }
+
+  /** Run loaded program from current IP till it halts or traps */
+  pub fn run(&mut self) -> Result<(), Trap<W>> {
+    while self.step()? {}
+    Ok(())
+  }
+
+  fn r(&self, x: W) -> Result<W, TrapKind> {
+    let r = x.to_usize();
+    self.reg.get(r).copied().ok_or(TrapKind::InvalidRegister(r))
+  }
+
+  fn set(&mut self, x: W, value: W) -> Result<(), TrapKind> {
+    let r = x.to_usize();
+    let reg = self.reg.get_mut(r).ok_or(TrapKind::InvalidRegister(r))?;
+    *reg = value;
+    Ok(())
+  }
+
+  fn add(&self, x: W, y: W) -> Result<W, TrapKind> {
+    self.overflow.add(x, y).ok_or(TrapKind::Overflow)
+  }
+
+  fn mul(&self, x: W, y: W) -> Result<W, TrapKind> {
+    self.overflow.mul(x, y).ok_or(TrapKind::Overflow)
+  }
+
+  // operations
+  fn addr(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.add(self.r(a)?, self.r(b)?)?)
+  }
+
+  fn addi(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.add(self.r(a)?, b)?)
+  }
+
+  fn mulr(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.mul(self.r(a)?, self.r(b)?)?)
+  }
+
+  fn muli(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.mul(self.r(a)?, b)?)
+  }
+
+  fn banr(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.r(a)? & self.r(b)?)
+  }
+
+  fn bani(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.r(a)? & b)
+  }
+
+  fn borr(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.r(a)? | self.r(b)?)
+  }
+
+  fn bori(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.r(a)? | b)
+  }
+
+  fn setr(&mut self, a: W, _: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, self.r(a)?)
+  }
+
+  fn seti(&mut self, a: W, _: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, a)
+  }
+
+  fn gtir(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, W::from_bool(a > self.r(b)?))
+  }
+
+  fn gtri(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, W::from_bool(self.r(a)? > b))
+  }
+
+  fn gtrr(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, W::from_bool(self.r(a)? > self.r(b)?))
+  }
+
+  fn eqir(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, W::from_bool(a == self.r(b)?))
+  }
+
+  fn eqri(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, W::from_bool(self.r(a)? == b))
+  }
+
+  fn eqrr(&mut self, a: W, b: W, c: W) -> Result<(), TrapKind> {
+    self.set(c, W::from_bool(self.r(a)? == self.r(b)?))
+  }
+}
@@ -0,0 +1,344 @@
+use crate::{Cpu, Snapshot, Trap, Word};
+use std::{
+  collections::BTreeSet,
+  fmt::{self, Display, Formatter},
+  io::{self, BufRead, Write},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Watch<W> {
+  Changes(usize),
+  Equals(usize, W),
+}
+
+impl<W: Display> Display for Watch<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Watch::Changes(r) => write!(f, "r{} changes", r),
+      Watch::Equals(r, v) => write!(f, "r{} == {}", r, v),
+    }
+  }
+}
+
+/// Reason execution stopped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stop<W> {
+  Breakpoint(usize),
+  Watch(usize),
+  Halted,
+  Stepped,
+  /// Instruction at IP can’t execute; the CPU stays there
+  Trap(Trap<W>),
+}
+
+/// Interactive debugger for an elfcode `Cpu` with breakpoints on IP values and
+/// watchpoints on registers.
+pub struct Debugger<W, const N: usize> {
+  pub cpu: Cpu<W, N>,
+  breakpoints: BTreeSet<usize>,
+  watches: Vec<Watch<W>>,
+  // saved with the step count at the time
+  snapshots: Vec<(Snapshot<W, N>, u64)>,
+  steps: u64,
+}
+
+// steps that can be undone unless the CPU keeps a history already
+const HISTORY: usize = 10_000;
+
+const HELP: &str = "\
+b N          set breakpoint at IP N
+d N          delete breakpoint at IP N
+w rN         watch register N changing
+w rN == V    watch register N becoming V
+u I          remove watch number I
+l            list breakpoints and watches
+s [COUNT]    step COUNT instructions (default 1)
+rs [COUNT]   step back COUNT instructions (default 1)
+c            continue till a breakpoint, a watch triggers or halt
+p            print registers
+i            print current instruction
+set rN V     set register N to V
+snap         save registers and IP
+restore I    go back to snapshot number I
+r            reset registers and IP
+q            quit
+An empty line repeats the last command.";
+
+impl<W: Word, const N: usize> Debugger<W, N> {
+  pub fn new(mut cpu: Cpu<W, N>) -> Self {
+    if cpu.history().is_none() {
+      cpu.enable_history(HISTORY);
+    }
+    Debugger {
+      cpu,
+      breakpoints: BTreeSet::new(),
+      watches: Vec::new(),
+      snapshots: Vec::new(),
+      steps: 0,
+    }
+  }
+
+  pub fn add_breakpoint(&mut self, ip: usize) {
+    self.breakpoints.insert(ip);
+  }
+
+  /** Step once; returns the first watch triggered, if any */
+  fn step_once(&mut self) -> Option<Stop<W>> {
+    let before = self.cpu.reg;
+    match self.cpu.step() {
+      Ok(true) => (),
+      Ok(false) => return Some(Stop::Halted),
+      Err(trap) => return Some(Stop::Trap(trap)),
+    }
+    self.steps += 1;
+    let after = &self.cpu.reg;
+    self
+      .watches
+      .iter()
+      .position(|w| match *w {
+        Watch::Changes(r) => before[r] != after[r],
+        // edge-triggered so continuing doesn’t stop right away again
+        Watch::Equals(r, v) => before[r] != v && after[r] == v,
+      })
+      .map(Stop::Watch)
+  }
+
+  /** Execute up to `count` instructions */
+  pub fn step(&mut self, count: usize) -> Stop<W> {
+    for _ in 0..count {
+      if let Some(stop) = self.step_once() {
+        return stop;
+      }
+      if self.cpu.is_halted() {
+        return Stop::Halted;
+      }
+    }
+    Stop::Stepped
+  }
+
+  /** Undo up to `count` instructions; returns how many were */
+  pub fn step_back(&mut self, count: usize) -> usize {
+    let undone = (0..count).take_while(|_| self.cpu.step_back()).count();
+    self.steps = self.steps.saturating_sub(undone as u64);
+    undone
+  }
+
+  /** Run till a breakpoint is reached, a watch triggers or the CPU halts */
+  pub fn cont(&mut self) -> Stop<W> {
+    loop {
+      if let Some(stop) = self.step_once() {
+        return stop;
+      }
+      let ip = self.cpu.ip();
+      if self.breakpoints.contains(&ip) {
+        return Stop::Breakpoint(ip);
+      }
+      if self.cpu.is_halted() {
+        return Stop::Halted;
+      }
+    }
+  }
+
+  fn print_registers(&self, out: &mut impl Write) -> io::Result<()> {
+    for (idx, r) in self.cpu.reg.iter().enumerate() {
+      match Some(idx) == self.cpu.ip_reg() {
+        true => write!(out, "r{}(ip)={} ", idx, r)?,
+        false => write!(out, "r{}={} ", idx, r)?,
+      }
+    }
+    writeln!(out, "| ip={} steps={}", self.cpu.ip(), self.steps)
+  }
+
+  fn print_current(&self, out: &mut impl Write) -> io::Result<()> {
+    match self.cpu.current() {
+      Some(i) => writeln!(out, "{:>3}: {}", self.cpu.ip(), i),
+      None => writeln!(out, "halted at ip={}", self.cpu.ip()),
+    }
+  }
+
+  fn print_stop(&self, stop: Stop<W>, out: &mut impl Write) -> io::Result<()> {
+    match stop {
+      Stop::Breakpoint(ip) => writeln!(out, "Breakpoint at {}", ip)?,
+      Stop::Watch(idx) => {
+        writeln!(out, "Watch {}: {}", idx, self.watches[idx])?
+      }
+      Stop::Halted => writeln!(out, "Program halted")?,
+      Stop::Trap(trap) => writeln!(out, "{}", trap)?,
+      Stop::Stepped => (),
+    }
+    self.print_registers(out)?;
+    self.print_current(out)
+  }
+
+  fn parse_reg(token: Option<&str>) -> Result<usize, String> {
+    token
+      .and_then(|t| t.strip_prefix('r'))
+      .and_then(|t| t.parse::<usize>().ok())
+      .filter(|&r| r < N)
+      .ok_or_else(|| format!("Expected a register in [r0, r{}]", N - 1))
+  }
+
+  fn parse_num<T: std::str::FromStr>(token: Option<&str>) -> Result<T, String> {
+    token
+      .and_then(|t| t.parse::<T>().ok())
+      .ok_or_else(|| "Expected a number".to_string())
+  }
+
+  /** Run one command; returns false when the user quits */
+  pub fn command(
+    &mut self,
+    line: &str,
+    out: &mut impl Write,
+  ) -> io::Result<bool> {
+    let mut tokens = line.split_ascii_whitespace();
+    let result: Result<(), String> = match tokens.next() {
+      Some("b") | Some("break") => {
+        Self::parse_num(tokens.next()).map(|ip| self.add_breakpoint(ip))
+      }
+      Some("d") | Some("delete") => {
+        Self::parse_num(tokens.next()).and_then(|ip: usize| {
+          match self.breakpoints.remove(&ip) {
+            true => Ok(()),
+            false => Err(format!("No breakpoint at {}", ip)),
+          }
+        })
+      }
+      Some("w") | Some("watch") => {
+        Self::parse_reg(tokens.next()).and_then(|r| match tokens.next() {
+          None => {
+            self.watches.push(Watch::Changes(r));
+            Ok(())
+          }
+          Some("==") => Self::parse_num(tokens.next()).map(|v| {
+            self.watches.push(Watch::Equals(r, v));
+          }),
+          Some(_) => Err("Expected `==` after register".to_string()),
+        })
+      }
+      Some("u") | Some("unwatch") => {
+        Self::parse_num(tokens.next()).and_then(|idx: usize| {
+          match idx < self.watches.len() {
+            true => {
+              self.watches.remove(idx);
+              Ok(())
+            }
+            false => Err(format!("No watch numbered {}", idx)),
+          }
+        })
+      }
+      Some("l") | Some("list") => {
+        for ip in &self.breakpoints {
+          writeln!(out, "Breakpoint at {}", ip)?;
+        }
+        for (idx, w) in self.watches.iter().enumerate() {
+          writeln!(out, "Watch {}: {}", idx, w)?;
+        }
+        Ok(())
+      }
+      Some("s") | Some("step") => {
+        let count = match tokens.next() {
+          Some(t) => t.parse::<usize>().map_err(|e| e.to_string()),
+          None => Ok(1),
+        };
+        match count {
+          Ok(count) => {
+            let stop = self.step(count);
+            self.print_stop(stop, out)?;
+            Ok(())
+          }
+          Err(e) => Err(e),
+        }
+      }
+      Some("rs") | Some("rstep") => {
+        let count = match tokens.next() {
+          Some(t) => t.parse::<usize>().map_err(|e| e.to_string()),
+          None => Ok(1),
+        };
+        match count {
+          Ok(count) => {
+            let undone = self.step_back(count);
+            if undone < count {
+              writeln!(out, "History exhausted after {} steps back", undone)?;
+            }
+            self.print_stop(Stop::Stepped, out)?;
+            Ok(())
+          }
+          Err(e) => Err(e),
+        }
+      }
+      Some("snap") => {
+        self.snapshots.push((self.cpu.snapshot(), self.steps));
+        writeln!(out, "Snapshot {}", self.snapshots.len() - 1)?;
+        Ok(())
+      }
+      Some("restore") => {
+        Self::parse_num(tokens.next()).and_then(|idx: usize| {
+          match self.snapshots.get(idx) {
+            Some(&(snapshot, steps)) => {
+              self.cpu.restore(&snapshot);
+              self.steps = steps;
+              Ok(())
+            }
+            None => Err(format!("No snapshot numbered {}", idx)),
+          }
+        })
+      }
+      Some("c") | Some("continue") => {
+        let stop = self.cont();
+        self.print_stop(stop, out)?;
+        Ok(())
+      }
+      Some("p") | Some("print") => {
+        self.print_registers(out)?;
+        Ok(())
+      }
+      Some("i") | Some("instr") => {
+        self.print_current(out)?;
+        Ok(())
+      }
+      Some("set") => Self::parse_reg(tokens.next()).and_then(|r| {
+        Self::parse_num(tokens.next()).map(|v| self.cpu.reg[r] = v)
+      }),
+      Some("r") | Some("reset") => {
+        self.cpu.clear();
+        self.steps = 0;
+        Ok(())
+      }
+      Some("h") | Some("help") => {
+        writeln!(out, "{}", HELP)?;
+        Ok(())
+      }
+      Some("q") | Some("quit") => return Ok(false),
+      Some(cmd) => Err(format!("Unknown command `{}`; try `h`", cmd)),
+      None => Ok(()),
+    };
+    if let Err(e) = result {
+      writeln!(out, "{}", e)?;
+    }
+    Ok(true)
+  }
+
+  /** Read commands from `input` till it ends or the user quits */
+  pub fn repl(
+    &mut self,
+    input: impl BufRead,
+    out: &mut impl Write,
+  ) -> io::Result<()> {
+    let mut last = String::new();
+    self.print_current(out)?;
+    write!(out, "(elfdbg) ")?;
+    out.flush()?;
+    for l in input.lines() {
+      let line = l?;
+      if !line.trim().is_empty() {
+        last = line;
+      }
+      if !self.command(&last, out)? {
+        break;
+      }
+      write!(out, "(elfdbg) ")?;
+      out.flush()?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,201 @@
+use crate::{Instruction, Overflow, Program, Word};
+use std::{
+  collections::BTreeSet,
+  fmt::{self, Display, Formatter, Write},
+};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Operand<W> {
+  Reg(usize),
+  Value(W),
+}
+
+impl<W: Display> Display for Operand<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Operand::Reg(r) => write!(f, "r{}", r),
+      Operand::Value(v) => write!(f, "{}", v),
+    }
+  }
+}
+
+/// Right-hand side of an instruction with constant operands folded.
+#[derive(Copy, Clone)]
+enum Expr<W> {
+  Value(W),
+  Reg(usize),
+  Binary(Operand<W>, &'static str, Operand<W>),
+}
+
+impl<W: Word> Expr<W> {
+  /** Build expression for instruction at `line`; IP register reads fold into
+   * `line` since that’s what the register holds while the instruction runs */
+  fn new(i: &Instruction<W>, line: usize, ip_reg: Option<usize>) -> Self {
+    let operand = |x: W, is_reg: bool| match is_reg {
+      true if Some(x.to_usize()) == ip_reg => {
+        Operand::Value(W::from_usize(line))
+      }
+      true => Operand::Reg(x.to_usize()),
+      false => Operand::Value(x),
+    };
+    let a = operand(i.a, i.op.reads_a());
+    let b = operand(i.b, i.op.reads_b());
+    match (i.op.symbol(), a, b) {
+      (None, Operand::Value(v), _) => Expr::Value(v),
+      (None, Operand::Reg(r), _) => Expr::Reg(r),
+      // leave overflowing constants unfolded whatever the CPU's mode
+      (Some(sym), Operand::Value(x), Operand::Value(y)) => {
+        match i.op.eval(x, y, Overflow::Checked) {
+          Some(v) => Expr::Value(v),
+          None => Expr::Binary(a, sym, b),
+        }
+      }
+      (Some(sym), a, b) => Expr::Binary(a, sym, b),
+    }
+  }
+
+  /** Register `r` if expression is `r + k` or `k + r`; with `k` */
+  fn as_offset(&self) -> Option<(usize, W)> {
+    match *self {
+      Expr::Binary(Operand::Reg(r), "+", Operand::Value(k))
+      | Expr::Binary(Operand::Value(k), "+", Operand::Reg(r)) => Some((r, k)),
+      _ => None,
+    }
+  }
+}
+
+impl<W: Display> Display for Expr<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Expr::Value(v) => write!(f, "{}", v),
+      Expr::Reg(r) => write!(f, "r{}", r),
+      Expr::Binary(a, sym, b) => write!(f, "{} {} {}", a, sym, b),
+    }
+  }
+}
+
+enum Stmt<W> {
+  Assign(usize, Expr<W>),
+  Goto(usize),
+  If(Expr<W>, usize),
+  Jump(Expr<W>),
+  Halt,
+  IfHalt(Expr<W>),
+}
+
+impl<W: Word> Display for Stmt<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Stmt::Assign(r, e) => write!(f, "r{} = {}", r, e),
+      Stmt::Goto(target) => write!(f, "goto L{}", target),
+      Stmt::If(cond, target) => write!(f, "if {} goto L{}", cond, target),
+      Stmt::Jump(e) => match e
+        .as_offset()
+        .and_then(|(r, k)| Some((r, k.checked_add(W::ONE)?)))
+      {
+        Some((r, k)) => write!(f, "goto r{} + {}", r, k),
+        None => write!(f, "goto ({}) + 1", e),
+      },
+      Stmt::Halt => f.write_str("halt"),
+      Stmt::IfHalt(cond) => write!(f, "if {} halt", cond),
+    }
+  }
+}
+
+/// Jump target of an IP write whose value is `v`; `None` if it halts.
+fn target<W: Word>(v: W, len: usize) -> Option<usize> {
+  v.to_usize().checked_add(1).filter(|&t| t < len)
+}
+
+fn statements<W: Word>(program: &Program<W>) -> Vec<Stmt<W>> {
+  let ip_reg = program.ip_reg;
+  let code = &program.instructions;
+  let len = code.len();
+  let mut stmts = code
+    .iter()
+    .enumerate()
+    .map(|(line, i)| {
+      let e = Expr::new(i, line, ip_reg);
+      match (Some(i.c.to_usize()) == ip_reg, e) {
+        (false, e) => Stmt::Assign(i.c.to_usize(), e),
+        (true, Expr::Value(v)) => match target(v, len) {
+          Some(t) => Stmt::Goto(t),
+          None => Stmt::Halt,
+        },
+        (true, e) => Stmt::Jump(e),
+      }
+    })
+    .collect::<Vec<_>>();
+
+  // `rF = cond; ip += rF` skips next line on a comparison’s result unless the
+  // second line is jumped into where `rF` might not hold a 0 or 1; when `rF`
+  // is also compared, the condition no longer holds once it’s written so the
+  // flag is tested instead
+  let targets = jump_targets(&stmts);
+  for line in 1..len {
+    let flag = match &stmts[line] {
+      Stmt::Jump(e) => match e.as_offset() {
+        Some((r, k)) if k.to_usize() == line => r,
+        _ => continue,
+      },
+      _ => continue,
+    };
+    let prev = &code[line - 1];
+    if prev.op.is_comparison()
+      && prev.c.to_usize() == flag
+      && !targets.contains(&line)
+    {
+      let clobbered = (prev.op.reads_a() && prev.a.to_usize() == flag)
+        || (prev.op.reads_b() && prev.b.to_usize() == flag);
+      let cond = match clobbered {
+        true => Expr::Reg(flag),
+        false => Expr::new(prev, line - 1, ip_reg),
+      };
+      stmts[line] = match line + 2 < len {
+        true => Stmt::If(cond, line + 2),
+        false => Stmt::IfHalt(cond),
+      };
+    }
+  }
+  stmts
+}
+
+fn jump_targets<W>(stmts: &[Stmt<W>]) -> BTreeSet<usize> {
+  stmts
+    .iter()
+    .filter_map(|s| match s {
+      Stmt::Goto(t) | Stmt::If(_, t) => Some(*t),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Renders `program` as pseudo-code; one line per instruction annotated with
+/// its line number and the original instruction.  IP writes are shown as
+/// `goto`, `if … goto` or `halt` and their targets are labelled `L<line>`.
+pub fn disassemble<W: Word>(program: &Program<W>) -> String {
+  let stmts = statements(program);
+  let labels = jump_targets(&stmts);
+  let mut out = String::new();
+  if let Some(r) = program.ip_reg {
+    writeln!(out, "#ip {}", r).unwrap();
+  }
+  for (line, (stmt, instr)) in
+    stmts.iter().zip(&program.instructions).enumerate()
+  {
+    let label = match labels.contains(&line) {
+      true => format!("L{}:", line),
+      false => String::new(),
+    };
+    writeln!(
+      out,
+      "{:>3}: {:<5} {:<28} ; {}",
+      line,
+      label,
+      stmt.to_string(),
+      instr
+    )
+    .unwrap();
+  }
+  out
+}
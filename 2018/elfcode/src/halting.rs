@@ -0,0 +1,158 @@
+use crate::{Compiled, Cpu, Instruction, Op, Word};
+use std::{collections::HashSet, error::Error};
+
+// instructions a taken comparison may run before halting
+const PROBE_STEPS: usize = 64;
+
+/// Input register value that makes a program halt.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Halt<W> {
+  pub value: W,
+  /// Comparison that sees `value`
+  pub line: usize,
+  /// Instructions executed till the program halts
+  pub steps: u64,
+}
+
+/// What a comparison checks the input register against.
+enum Other<W> {
+  Reg(usize),
+  Value(W),
+}
+
+/** Comparisons against register `input`, by line; errors if the program
+ * uses the register in any other way as the analysis would be unsound */
+fn comparisons<W: Word>(
+  code: &[Instruction<W>],
+  input: usize,
+  ip_reg: Option<usize>,
+) -> Result<Vec<Option<Other<W>>>, Box<dyn Error>> {
+  if ip_reg == Some(input) {
+    return Err(format!("Register {} is bound to the IP", input).into());
+  }
+  code
+    .iter()
+    .enumerate()
+    .map(|(line, i)| {
+      let reads_a = i.op.reads_a() && i.a.to_usize() == input;
+      let reads_b = i.op.reads_b() && i.b.to_usize() == input;
+      match (i.op, reads_a, reads_b) {
+        _ if i.c.to_usize() == input => {
+          Err(format!("Register {} written at line {}", input, line).into())
+        }
+        (_, false, false) => Ok(None),
+        (Op::Eqrr, true, false) => Ok(Some(Other::Reg(i.b.to_usize()))),
+        (Op::Eqrr, false, true) => Ok(Some(Other::Reg(i.a.to_usize()))),
+        (Op::Eqri, true, _) => Ok(Some(Other::Value(i.b))),
+        (Op::Eqir, _, true) => Ok(Some(Other::Value(i.a))),
+        _ => Err(
+          format!(
+            "Register {} read other than by an equality comparison at line {}",
+            input, line
+          )
+          .into(),
+        ),
+      }
+    })
+    .collect()
+}
+
+/// Values of register `input` that make the program loaded in `cpu` halt,
+/// earliest halting first.
+///
+/// The program may read `input` only to compare it for equality and must
+/// never write it, as in day 21.  It runs from the current state with each
+/// comparison made false, noting the values compared; a compared value halts
+/// the program if taking the comparison reaches the end within a few
+/// instructions.  The run ends when the program halts by itself or reaches a
+/// comparison in a state seen before, after which no new values can come up.
+/// CPUs with profiling enabled are interpreted so the profile is complete.
+pub fn halting_values<W: Word + 'static, const N: usize>(
+  cpu: &mut Cpu<W, N>,
+  input: usize,
+) -> Result<Vec<Halt<W>>, Box<dyn Error>> {
+  scan(cpu, input, false)
+}
+
+/** Value of register `input` that halts the program soonest, as found by
+ * `halting_values` but stopping at the first one; `None` if none does */
+pub fn first_halting_value<W: Word + 'static, const N: usize>(
+  cpu: &mut Cpu<W, N>,
+  input: usize,
+) -> Result<Option<Halt<W>>, Box<dyn Error>> {
+  Ok(scan(cpu, input, true)?.pop())
+}
+
+fn scan<W: Word + 'static, const N: usize>(
+  cpu: &mut Cpu<W, N>,
+  input: usize,
+  first: bool,
+) -> Result<Vec<Halt<W>>, Box<dyn Error>> {
+  if input >= N {
+    return Err(format!("Invalid register {}", input).into());
+  }
+  let compared = comparisons(cpu.program(), input, cpu.ip_reg())?;
+  let stops = (0..compared.len())
+    .filter(|&line| compared[line].is_some())
+    .collect::<Vec<_>>();
+  let compiled = Compiled::new(cpu, &stops)?;
+  let is_stop = |ip: usize| matches!(compared.get(ip), Some(Some(_)));
+
+  let mut halts = Vec::new();
+  let mut halting = HashSet::new();
+  let mut seen = HashSet::new();
+  let mut steps = 0;
+  while !cpu.is_halted() {
+    let line = cpu.ip();
+    if let Some(other) = &compared[line] {
+      // IP register reads as the line while the instruction runs
+      let value = match *other {
+        Other::Reg(r) if Some(r) == cpu.ip_reg() => W::from_usize(line),
+        Other::Reg(r) => cpu.reg[r],
+        Other::Value(v) => v,
+      };
+      let mut state = cpu.reg;
+      state[input] = W::ZERO;
+      if !seen.insert((line, state)) {
+        break;
+      }
+      // take the comparison and see if the program ends shortly
+      let saved = cpu.snapshot();
+      cpu.reg[input] = value;
+      let mut probe = 0;
+      while probe < PROBE_STEPS && cpu.step()? {
+        probe += 1;
+        if is_stop(cpu.ip()) {
+          break;
+        }
+      }
+      if cpu.is_halted() && halting.insert(value) {
+        halts.push(Halt {
+          value,
+          line,
+          steps: steps + probe as u64,
+        });
+      }
+      cpu.restore(&saved);
+      if first && !halts.is_empty() {
+        break;
+      }
+      // any other value makes the comparison false
+      cpu.reg[input] = value.wrapping_add(W::ONE);
+    }
+    steps += match cpu.profile() {
+      Some(_) => {
+        let mut count = 0;
+        while cpu.step()? {
+          count += 1;
+          if is_stop(cpu.ip()) {
+            break;
+          }
+        }
+        count
+      }
+      None => compiled.run(cpu)?,
+    };
+  }
+  Ok(halts)
+}
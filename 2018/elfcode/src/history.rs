@@ -0,0 +1,60 @@
+use crate::Overflow;
+use std::collections::VecDeque;
+
+/// Full CPU state short of the program: registers, IP, its binding and the
+/// overflow mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot<W, const N: usize> {
+  pub reg: [W; N],
+  pub ip: usize,
+  pub ip_reg: Option<usize>,
+  pub overflow: Overflow,
+}
+
+/// Bounded undo log of states before recent steps; oldest are dropped first.
+#[derive(Clone, Debug)]
+pub struct History<W, const N: usize> {
+  states: VecDeque<Snapshot<W, N>>,
+  capacity: usize,
+}
+
+impl<W, const N: usize> History<W, N> {
+  pub fn new(capacity: usize) -> Self {
+    History {
+      states: VecDeque::with_capacity(capacity),
+      capacity,
+    }
+  }
+
+  /** Note state before a step */
+  pub fn push(&mut self, state: Snapshot<W, N>) {
+    if self.capacity == 0 {
+      return;
+    }
+    if self.states.len() == self.capacity {
+      self.states.pop_front();
+    }
+    self.states.push_back(state);
+  }
+
+  /** State before the latest step still logged */
+  pub fn pop(&mut self) -> Option<Snapshot<W, N>> {
+    self.states.pop_back()
+  }
+
+  pub fn len(&self) -> usize {
+    self.states.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.states.is_empty()
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  pub fn clear(&mut self) {
+    self.states.clear();
+  }
+}
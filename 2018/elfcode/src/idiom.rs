@@ -0,0 +1,233 @@
+use crate::{math, Cpu, Instruction, Op, Trap, Word};
+use std::fmt::{self, Display, Formatter};
+
+// Roles registers play in a pattern; bound to actual registers when matching.
+const ACC: usize = 0;
+const OUTER: usize = 1;
+const INNER: usize = 2;
+const TMP: usize = 3;
+const TARGET: usize = 4;
+const ROLE_COUNT: usize = 5;
+
+#[derive(Copy, Clone)]
+enum Arg {
+  Role(usize),
+  Ip,
+  Imm(usize),
+  Any,
+  /// Immediate that makes the IP jump to `entry + n`
+  Target(usize),
+}
+
+#[derive(Copy, Clone)]
+struct Pattern {
+  op: Op,
+  a: Arg,
+  b: Arg,
+  c: Arg,
+}
+
+const fn p(op: Op, a: Arg, b: Arg, c: Arg) -> Pattern {
+  Pattern { op, a, b, c }
+}
+
+use Arg::*;
+
+/// Brute force sum of divisors; day 19 computes `ACC += σ(TARGET)` by
+/// ```text
+/// for OUTER in 1..=TARGET {
+///   for INNER in 1..=TARGET {
+///     if OUTER * INNER == TARGET { ACC += OUTER }
+///   }
+/// }
+/// ```
+const DIVISOR_SUM: [Pattern; 15] = [
+  p(Op::Seti, Imm(1), Any, Role(OUTER)),
+  p(Op::Seti, Imm(1), Any, Role(INNER)),
+  p(Op::Mulr, Role(OUTER), Role(INNER), Role(TMP)),
+  p(Op::Eqrr, Role(TMP), Role(TARGET), Role(TMP)),
+  p(Op::Addr, Role(TMP), Ip, Ip),
+  p(Op::Addi, Ip, Imm(1), Ip),
+  p(Op::Addr, Role(OUTER), Role(ACC), Role(ACC)),
+  p(Op::Addi, Role(INNER), Imm(1), Role(INNER)),
+  p(Op::Gtrr, Role(INNER), Role(TARGET), Role(TMP)),
+  p(Op::Addr, Ip, Role(TMP), Ip),
+  p(Op::Seti, Target(2), Any, Ip),
+  p(Op::Addi, Role(OUTER), Imm(1), Role(OUTER)),
+  p(Op::Gtrr, Role(OUTER), Role(TARGET), Role(TMP)),
+  p(Op::Addr, Role(TMP), Ip, Ip),
+  p(Op::Seti, Target(1), Any, Ip),
+];
+
+type Roles = [Option<usize>; ROLE_COUNT];
+
+struct Matcher<'a, W> {
+  code: &'a [Instruction<W>],
+  entry: usize,
+  ip_reg: usize,
+}
+
+impl<W: Word> Matcher<'_, W> {
+  fn arg(&self, arg: Arg, operand: W, roles: &mut Roles) -> bool {
+    let value = operand.to_usize();
+    match arg {
+      Role(k) => match roles[k] {
+        Some(r) => r == value,
+        None => {
+          let free = value != self.ip_reg && !roles.contains(&Some(value));
+          if free {
+            roles[k] = Some(value);
+          }
+          free
+        }
+      },
+      Ip => value == self.ip_reg,
+      Imm(v) => value == v,
+      Any => true,
+      Target(n) => value.checked_add(1) == Some(self.entry + n),
+    }
+  }
+
+  /** Match `patterns` from `line` on; tries both operand orders of
+   * commutative operations */
+  fn bind(
+    &self,
+    line: usize,
+    patterns: &[Pattern],
+    roles: Roles,
+  ) -> Option<Roles> {
+    let (pat, rest) = match patterns.split_first() {
+      Some(split) => split,
+      None => return Some(roles),
+    };
+    let i = self.code.get(line)?;
+    if i.op != pat.op {
+      return None;
+    }
+    let commutes =
+      matches!(pat.op, Op::Addr | Op::Mulr | Op::Banr | Op::Borr | Op::Eqrr);
+    let orders: &[(Arg, Arg)] = match commutes {
+      true => &[(pat.a, pat.b), (pat.b, pat.a)],
+      false => &[(pat.a, pat.b)],
+    };
+    orders.iter().find_map(|&(a, b)| {
+      let mut roles = roles;
+      (self.arg(a, i.a, &mut roles)
+        && self.arg(b, i.b, &mut roles)
+        && self.arg(pat.c, i.c, &mut roles))
+      .then(|| self.bind(line + 1, rest, roles))
+      .flatten()
+    })
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IdiomKind {
+  DivisorSum,
+}
+
+/// Known loop found in a program, replaceable by its closed form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Idiom {
+  pub kind: IdiomKind,
+  /// IP at which the loop is entered
+  pub entry: usize,
+  /// IP at which control leaves the loop
+  pub exit: usize,
+  roles: [usize; ROLE_COUNT],
+}
+
+impl Display for Idiom {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self.kind {
+      IdiomKind::DivisorSum => write!(
+        f,
+        "[{}, {}): r{} += sum of divisors of r{}",
+        self.entry, self.exit, self.roles[ACC], self.roles[TARGET]
+      ),
+    }
+  }
+}
+
+impl Idiom {
+  /** Find known loops in `code` whatever registers or offsets they use */
+  pub fn find<W: Word>(
+    code: &[Instruction<W>],
+    ip_reg: Option<usize>,
+  ) -> Vec<Self> {
+    let ip_reg = match ip_reg {
+      Some(r) => r,
+      None => return Vec::new(), // loops need jumps
+    };
+    (0..code.len())
+      .filter_map(|entry| {
+        let matcher = Matcher {
+          code,
+          entry,
+          ip_reg,
+        };
+        matcher
+          .bind(entry, &DIVISOR_SUM, [None; ROLE_COUNT])
+          .map(|roles| Idiom {
+            kind: IdiomKind::DivisorSum,
+            entry,
+            exit: entry + DIVISOR_SUM.len(),
+            roles: roles.map(Option::unwrap),
+          })
+      })
+      .collect()
+  }
+
+  /** Put `cpu` in the state it’d be in after running the loop; returns false
+   * and leaves `cpu` untouched when the closed form doesn’t apply */
+  pub fn apply<W: Word, const N: usize>(&self, cpu: &mut Cpu<W, N>) -> bool {
+    if cpu.ip() != self.entry || self.roles.iter().any(|&r| r >= N) {
+      return false;
+    }
+    match self.kind {
+      IdiomKind::DivisorSum => {
+        let target = cpu.reg[self.roles[TARGET]];
+        let n = target.to_usize();
+        // loop runs differently for 0 and never ends for the largest word;
+        // leave those to the interpreter
+        if n == 0 || target.checked_add(W::ONE).is_none() {
+          return false;
+        }
+        let total = math::factors(n as u64).iter().sum::<u64>();
+        let sum = W::from_usize(total as usize);
+        // an overflowing sum is left to the interpreter to apply the CPU's
+        // overflow mode; `OUTER * INNER` is assumed not to overflow though
+        let acc = match cpu.reg[self.roles[ACC]].checked_add(sum) {
+          Some(acc) if sum.to_usize() as u64 == total => acc,
+          _ => return false,
+        };
+        cpu.reg[self.roles[ACC]] = acc;
+        // loop counters end one past target; last comparison was true
+        cpu.reg[self.roles[OUTER]] = W::from_usize(n + 1);
+        cpu.reg[self.roles[INNER]] = W::from_usize(n + 1);
+        cpu.reg[self.roles[TMP]] = W::ONE;
+      }
+    }
+    if let Some(r) = cpu.ip_reg() {
+      cpu.reg[r] = W::from_usize(self.exit - 1);
+    }
+    cpu.set_ip(self.exit);
+    true
+  }
+}
+
+impl<W: Word, const N: usize> Cpu<W, N> {
+  /** Run loaded program from current IP till it halts or traps; loops in
+   * `idioms` are short-circuited with their closed forms */
+  pub fn run_with(&mut self, idioms: &[Idiom]) -> Result<(), Trap<W>> {
+    loop {
+      let ip = self.ip();
+      if idioms.iter().any(|i| i.entry == ip && i.apply(self)) {
+        continue;
+      }
+      if !self.step()? {
+        return Ok(());
+      }
+    }
+  }
+}
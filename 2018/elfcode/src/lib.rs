@@ -0,0 +1,37 @@
+//! Virtual machine for the _elfcode_ assembly language shared by days 16, 19
+//! and 21.
+
+mod asm;
+mod compile;
+mod cpu;
+mod debugger;
+mod disasm;
+mod halting;
+mod history;
+mod idiom;
+mod math;
+mod op;
+mod opcodes;
+mod options;
+mod profile;
+mod program;
+mod symbolic;
+mod trap;
+mod word;
+
+pub use asm::assemble;
+pub use compile::Compiled;
+pub use cpu::Cpu;
+pub use debugger::{Debugger, Stop};
+pub use disasm::disassemble;
+pub use halting::{first_halting_value, halting_values, Halt};
+pub use history::{History, Snapshot};
+pub use idiom::{Idiom, IdiomKind};
+pub use op::Op;
+pub use opcodes::OpcodeMap;
+pub use options::{Image, Options};
+pub use profile::{Loop, Profile};
+pub use program::{Instruction, Program};
+pub use symbolic::{explore, BinOp, Constraint, Limits, Outcome, Path, Sym};
+pub use trap::{Trap, TrapKind};
+pub use word::{Overflow, Word};
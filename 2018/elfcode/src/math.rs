@@ -5,7 +5,7 @@ pub mod prime {
   /// e.g. extract all evenness in n by passing `(n, 2)`
   fn extract(n: &mut u64, f: u64) -> u32 {
     let mut count = 0;
-    while *n % f == 0 {
+    while n.is_multiple_of(f) {
       *n /= f;
       count += 1;
     }
@@ -34,7 +34,7 @@ pub mod prime {
     // https://stackoverflow.com/q/5811151/183120
     while (f * f) <= n {
       let i = extract(&mut m, f);
-      v.extend(iter::repeat(f).take(i as usize));
+      v.extend(iter::repeat_n(f, i as usize));
       f += 2;
       // Optimisation 2: skip even numbers since all evenness
       // has been extracted from n.
@@ -69,6 +69,10 @@ where
 /// Returns all factors of `n`.
 pub fn factors(n: u64) -> Vec<u64> {
   let primes = prime::factors(n);
+  // 1 has no prime factors
+  if primes.is_empty() {
+    return vec![1];
+  }
   // prime::factors(90) = 2 * 3 * 3 * 5
   // seeds = 1, 2, 1, 3, 9, 1, 5; 3-tuple cartesean product gives result
   // result: 1, 2, 3, 5, 6, 9, 10, 15, 18, 30, 45, 90
@@ -0,0 +1,132 @@
+use crate::{Overflow, Word};
+use std::{
+  error::Error,
+  fmt::{self, Display, Formatter},
+  str::FromStr,
+};
+
+/// Elfcode operations in the order the puzzle (day 16) lists them.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Op {
+  Addr,
+  Addi,
+  Mulr,
+  Muli,
+  Banr,
+  Bani,
+  Borr,
+  Bori,
+  Setr,
+  Seti,
+  Gtir,
+  Gtri,
+  Gtrr,
+  Eqir,
+  Eqri,
+  Eqrr,
+}
+
+impl Op {
+  pub const COUNT: usize = 16;
+
+  pub const ALL: [Op; Op::COUNT] = [
+    Op::Addr,
+    Op::Addi,
+    Op::Mulr,
+    Op::Muli,
+    Op::Banr,
+    Op::Bani,
+    Op::Borr,
+    Op::Bori,
+    Op::Setr,
+    Op::Seti,
+    Op::Gtir,
+    Op::Gtri,
+    Op::Gtrr,
+    Op::Eqir,
+    Op::Eqri,
+    Op::Eqrr,
+  ];
+
+  const NAMES: [&'static str; Op::COUNT] = [
+    "addr", "addi", "mulr", "muli", "banr", "bani", "borr", "bori", "setr",
+    "seti", "gtir", "gtri", "gtrr", "eqir", "eqri", "eqrr",
+  ];
+
+  pub fn name(self) -> &'static str {
+    Self::NAMES[self as usize]
+  }
+
+  /// Result of the operation given values of its operands; register operands
+  /// should already be read.  `None` if it overflows in `Checked` mode.
+  pub fn eval<W: Word>(self, a: W, b: W, overflow: Overflow) -> Option<W> {
+    match self {
+      Op::Addr | Op::Addi => overflow.add(a, b),
+      Op::Mulr | Op::Muli => overflow.mul(a, b),
+      Op::Banr | Op::Bani => Some(a & b),
+      Op::Borr | Op::Bori => Some(a | b),
+      Op::Setr | Op::Seti => Some(a),
+      Op::Gtir | Op::Gtri | Op::Gtrr => Some(W::from_bool(a > b)),
+      Op::Eqir | Op::Eqri | Op::Eqrr => Some(W::from_bool(a == b)),
+    }
+  }
+
+  /// Infix operator symbol; `None` for the `set` family.
+  pub fn symbol(self) -> Option<&'static str> {
+    match self {
+      Op::Addr | Op::Addi => Some("+"),
+      Op::Mulr | Op::Muli => Some("*"),
+      Op::Banr | Op::Bani => Some("&"),
+      Op::Borr | Op::Bori => Some("|"),
+      Op::Setr | Op::Seti => None,
+      Op::Gtir | Op::Gtri | Op::Gtrr => Some(">"),
+      Op::Eqir | Op::Eqri | Op::Eqrr => Some("=="),
+    }
+  }
+
+  pub fn is_comparison(self) -> bool {
+    matches!(
+      self,
+      Op::Gtir | Op::Gtri | Op::Gtrr | Op::Eqir | Op::Eqri | Op::Eqrr
+    )
+  }
+
+  /// True if operand `a` names a register.
+  pub fn reads_a(self) -> bool {
+    !matches!(self, Op::Seti | Op::Gtir | Op::Eqir)
+  }
+
+  /// True if operand `b` names a register.
+  pub fn reads_b(self) -> bool {
+    matches!(
+      self,
+      Op::Addr
+        | Op::Mulr
+        | Op::Banr
+        | Op::Borr
+        | Op::Gtir
+        | Op::Gtrr
+        | Op::Eqir
+        | Op::Eqrr
+    )
+  }
+}
+
+impl Display for Op {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+impl FromStr for Op {
+  type Err = Box<dyn Error>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::NAMES
+      .iter()
+      .position(|&name| name == s)
+      .map(|idx| Self::ALL[idx])
+      .ok_or_else(|| format!("Unrecognized operation name: {}", s).into())
+  }
+}
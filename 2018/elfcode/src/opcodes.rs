@@ -0,0 +1,130 @@
+use crate::{Op, Program, Word};
+use std::{
+  error::Error,
+  fmt::{self, Display, Formatter},
+  str::FromStr,
+};
+
+/// Operation each numeric opcode stands for, as resolved by day 16.
+///
+/// Written as one `name: opcode` line per operation, which is what day 16
+/// saves with `--opcodes` and days 19 and 21 read with it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OpcodeMap {
+  ops: [Op; Op::COUNT],
+}
+
+impl OpcodeMap {
+  /** Map from `ops[opcode]`; fails unless every operation appears once */
+  pub fn new(ops: [Op; Op::COUNT]) -> Result<Self, Box<dyn Error>> {
+    match Op::ALL.iter().find(|op| !ops.contains(op)) {
+      Some(op) => Err(format!("Opcode map lacks operation {}", op).into()),
+      None => Ok(OpcodeMap { ops }),
+    }
+  }
+
+  pub fn op(&self, opcode: usize) -> Option<Op> {
+    self.ops.get(opcode).copied()
+  }
+
+  pub fn opcode(&self, op: Op) -> usize {
+    self.ops.iter().position(|&o| o == op).unwrap()
+  }
+
+  /** Parses numeric elfcode e.g. `3 4 16 4`, with an optional `#ip N` line */
+  pub fn decode<W: Word>(
+    &self,
+    input: &str,
+  ) -> Result<Program<W>, Box<dyn Error>> {
+    Program::parse_with(input, &|token| {
+      token
+        .parse()
+        .ok()
+        .and_then(|opcode| self.op(opcode))
+        .ok_or_else(|| format!("Unrecognized opcode: {}", token).into())
+    })
+  }
+
+  /** Numeric elfcode for `program`; inverse of `decode` */
+  pub fn encode<W: Display>(&self, program: &Program<W>) -> String {
+    let mut code = program
+      .ip_reg
+      .map(|r| format!("#ip {}\n", r))
+      .unwrap_or_default();
+    for i in &program.instructions {
+      code += &format!("{} {} {} {}\n", self.opcode(i.op), i.a, i.b, i.c);
+    }
+    code
+  }
+}
+
+impl Default for OpcodeMap {
+  /** Mapping of the puzzle input these solutions were written against */
+  fn default() -> Self {
+    OpcodeMap {
+      ops: [
+        Op::Borr,
+        Op::Addr,
+        Op::Eqrr,
+        Op::Addi,
+        Op::Eqri,
+        Op::Eqir,
+        Op::Gtri,
+        Op::Mulr,
+        Op::Setr,
+        Op::Gtir,
+        Op::Muli,
+        Op::Banr,
+        Op::Seti,
+        Op::Gtrr,
+        Op::Bani,
+        Op::Bori,
+      ],
+    }
+  }
+}
+
+impl Display for OpcodeMap {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    for (opcode, op) in self.ops.iter().enumerate() {
+      writeln!(f, "{}: {}", op, opcode)?;
+    }
+    Ok(())
+  }
+}
+
+impl FromStr for OpcodeMap {
+  type Err = Box<dyn Error>;
+
+  /** Parses `name: opcode` lines, one for each operation in any order */
+  fn from_str(input: &str) -> Result<Self, Self::Err> {
+    let mut ops = [None; Op::COUNT];
+    for (i, line) in input.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() {
+        continue;
+      }
+      let error = |msg: &str| format!("{} at line {}", msg, i + 1);
+      let (name, opcode) = line
+        .split_once(':')
+        .ok_or_else(|| error("Expected `name: opcode`"))?;
+      let op = name
+        .trim()
+        .parse::<Op>()
+        .map_err(|e| error(&e.to_string()))?;
+      let slot = opcode
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|opcode| ops.get_mut(opcode))
+        .ok_or_else(|| error("Invalid opcode"))?;
+      if slot.replace(op).is_some() {
+        return Err(error("Duplicate opcode").into());
+      }
+    }
+    match ops.iter().position(Option::is_none) {
+      Some(opcode) => Err(format!("Opcode map lacks opcode {}", opcode).into()),
+      None => Self::new(ops.map(Option::unwrap)),
+    }
+  }
+}
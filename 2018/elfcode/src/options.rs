@@ -0,0 +1,67 @@
+use crate::{Cpu, OpcodeMap, Overflow, Program, Word};
+use std::{env, error::Error, fs};
+
+/// How solvers read and run a program.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+  /// Decode numeric opcodes with this map instead of parsing mnemonics
+  pub opcodes: Option<OpcodeMap>,
+  pub overflow: Overflow,
+}
+
+impl Options {
+  /** Options from `--numeric`, `--opcodes FILE` (day 16’s output; default
+   * map otherwise) and `--overflow MODE` on the command line */
+  pub fn from_args() -> Result<Self, Box<dyn Error>> {
+    let option = |flag| env::args().skip_while(|arg| arg != flag).nth(1);
+    let mut opts = Options::default();
+    if env::args().any(|arg| arg == "--numeric") {
+      opts.opcodes = Some(match option("--opcodes") {
+        Some(path) => fs::read_to_string(path)?.parse()?,
+        None => OpcodeMap::default(),
+      });
+    }
+    if let Some(mode) = option("--overflow") {
+      opts.overflow = mode.parse()?;
+    }
+    Ok(opts)
+  }
+}
+
+/// Program checked to load on a CPU of `N` registers, with the overflow mode
+/// to run it in.
+#[derive(Clone, Debug)]
+pub struct Image<W, const N: usize> {
+  pub program: Program<W>,
+  pub overflow: Overflow,
+}
+
+impl<W: Word, const N: usize> Image<W, N> {
+  /** Program from mnemonic text or, with `opts.opcodes`, from numeric
+   * opcodes mapped as per it e.g. day 16’s output; the IP must be bound */
+  pub fn parse(input: &str, opts: &Options) -> Result<Self, Box<dyn Error>> {
+    let program: Program<W> = match opts.opcodes {
+      Some(opcodes) => opcodes.decode(input)?,
+      None => input.parse()?,
+    };
+    if program.ip_reg.is_none() {
+      return Err("Expected macro binding IP to a register".into());
+    }
+    // check the IP binding once so `cpu()` can't fail
+    Cpu::<W, N>::new().load(program.clone())?;
+    Ok(Image {
+      program,
+      overflow: opts.overflow,
+    })
+  }
+
+  /** CPU with the program loaded, set to the chosen overflow mode */
+  pub fn cpu(&self) -> Cpu<W, N> {
+    let mut cpu = Cpu::new();
+    cpu
+      .load(self.program.clone())
+      .expect("IP binding is checked in `parse`");
+    cpu.set_overflow(self.overflow);
+    cpu
+  }
+}
@@ -0,0 +1,93 @@
+use std::{
+  collections::HashMap,
+  fmt::{self, Display, Formatter},
+};
+
+/// Loop found from a back-edge i.e. a jump from `end` to `start <= end`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Loop {
+  pub start: usize,
+  pub end: usize,
+  /// Times the back-edge was taken
+  pub iterations: u64,
+  /// Instructions executed within `[start, end]`
+  pub hits: u64,
+}
+
+/// Execution profile of a program: per-line hit counts and back-edges.
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+  hits: Vec<u64>,
+  back_edges: HashMap<(usize, usize), u64>,
+}
+
+impl Profile {
+  pub fn new(len: usize) -> Self {
+    Profile {
+      hits: vec![0; len],
+      back_edges: HashMap::new(),
+    }
+  }
+
+  /** Note execution of line `from` with control moving to `to`; lines past
+   * the program's end are ignored */
+  pub fn record(&mut self, from: usize, to: usize) {
+    let h = match self.hits.get_mut(from) {
+      Some(h) => h,
+      None => return,
+    };
+    *h += 1;
+    if to <= from {
+      *self.back_edges.entry((from, to)).or_insert(0) += 1;
+    }
+  }
+
+  pub fn hits(&self) -> &[u64] {
+    &self.hits
+  }
+
+  pub fn total(&self) -> u64 {
+    self.hits.iter().sum()
+  }
+
+  /** Loops ranked by instructions executed within them; hottest first */
+  pub fn hot_loops(&self) -> Vec<Loop> {
+    let mut loops = self
+      .back_edges
+      .iter()
+      .map(|(&(end, start), &iterations)| Loop {
+        start,
+        end,
+        iterations,
+        hits: self.hits[start..=end].iter().sum(),
+      })
+      .collect::<Vec<_>>();
+    loops.sort_unstable_by_key(|l| (u64::MAX - l.hits, l.start, l.end));
+    loops
+  }
+}
+
+impl Display for Profile {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let total = self.total().max(1) as f64;
+    writeln!(f, "Line        Hits      %")?;
+    for (line, &h) in self.hits.iter().enumerate() {
+      writeln!(f, "{:>4} {:>11} {:>6.2}", line, h, h as f64 * 100.0 / total)?;
+    }
+    writeln!(f)?;
+    writeln!(f, "Rank   Range          Iterations        Hits      %")?;
+    for (rank, l) in self.hot_loops().iter().enumerate() {
+      writeln!(
+        f,
+        "{:>4}   [{:>3}, {:>3}]   {:>13} {:>11} {:>6.2}",
+        rank + 1,
+        l.start,
+        l.end,
+        l.iterations,
+        l.hits,
+        l.hits as f64 * 100.0 / total
+      )?;
+    }
+    Ok(())
+  }
+}
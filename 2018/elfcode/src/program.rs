@@ -0,0 +1,126 @@
+use crate::{Op, Word};
+use std::{
+  error::Error,
+  fmt::{self, Display, Formatter},
+  str::FromStr,
+};
+
+// turns an instruction's first token into its operation
+pub(crate) type Decode<'a> = &'a dyn Fn(&str) -> Result<Op, Box<dyn Error>>;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction<W> {
+  pub op: Op,
+  pub a: W,
+  pub b: W,
+  pub c: W,
+}
+
+impl<W: Word> Instruction<W> {
+  pub fn new(op: Op, a: W, b: W, c: W) -> Self {
+    Instruction { op, a, b, c }
+  }
+}
+
+impl<W: Display> Display for Instruction<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{} {} {} {}", self.op, self.a, self.b, self.c)
+  }
+}
+
+impl<W: Word> Instruction<W> {
+  /** Parses `op a b c` with `op` turned into an operation by `decode` */
+  pub(crate) fn parse_with(
+    line: &str,
+    decode: Decode,
+  ) -> Result<Self, Box<dyn Error>> {
+    let tokens = line.split_ascii_whitespace().collect::<Vec<&str>>();
+    if tokens.len() != 4 {
+      return Err(Box::<dyn Error>::from(
+        "Invalid input: unexpected number of tokens in instruction",
+      ));
+    }
+    let op = decode(tokens[0])?;
+    let mut args = [W::ZERO; 3];
+    // following can’t be done in a functional fashion since iter::map’s closure
+    // can’t do returning of a Result from this function
+    for (arg, &t) in args.iter_mut().zip(&tokens[1..4]) {
+      *arg = t
+        .parse::<W>()
+        .map_err(|_| format!("Invalid argument to instruction: {}", t))?;
+    }
+    Ok(Instruction::new(op, args[0], args[1], args[2]))
+  }
+}
+
+impl<W: Word> FromStr for Instruction<W> {
+  type Err = Box<dyn Error>;
+
+  /** Parses `opname a b c` e.g. `addi 4 16 4` */
+  fn from_str(line: &str) -> Result<Self, Self::Err> {
+    Self::parse_with(line, &str::parse)
+  }
+}
+
+/// Elfcode program with an optional `#ip N` binding.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program<W> {
+  pub ip_reg: Option<usize>,
+  pub instructions: Vec<Instruction<W>>,
+}
+
+impl<W: Display> Display for Program<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    if let Some(r) = self.ip_reg {
+      writeln!(f, "#ip {}", r)?;
+    }
+    for i in &self.instructions {
+      writeln!(f, "{}", i)?;
+    }
+    Ok(())
+  }
+}
+
+impl<W: Word> Program<W> {
+  /** Parses like `from_str` but with operations decoded by `decode` */
+  pub(crate) fn parse_with(
+    input: &str,
+    decode: Decode,
+  ) -> Result<Self, Box<dyn Error>> {
+    let mut program = Program {
+      ip_reg: None,
+      instructions: Vec::with_capacity(64),
+    };
+    for (i, line) in input.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() {
+        continue;
+      }
+      if let Some(reg) = line.strip_prefix("#ip") {
+        if i != 0 {
+          return Err(Box::<dyn Error>::from(format!(
+            "Invalid input: IP binding not on first line but at line {}",
+            i + 1
+          )));
+        }
+        program.ip_reg = Some(reg.trim().parse().map_err(|_| {
+          "Invalid input: unable to parse register specification."
+        })?);
+        continue;
+      }
+      let instr = Instruction::parse_with(line, decode)
+        .map_err(|e| format!("{} at line {}", e, i + 1))?;
+      program.instructions.push(instr);
+    }
+    Ok(program)
+  }
+}
+
+impl<W: Word> FromStr for Program<W> {
+  type Err = Box<dyn Error>;
+
+  /** Parses an optional `#ip N` line followed by one instruction per line */
+  fn from_str(input: &str) -> Result<Self, Self::Err> {
+    Self::parse_with(input, &str::parse)
+  }
+}
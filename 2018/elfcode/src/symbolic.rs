@@ -0,0 +1,400 @@
+use crate::{Cpu, Op, Overflow, Word};
+use std::{
+  collections::HashSet,
+  fmt::{self, Display, Formatter},
+  rc::Rc,
+};
+
+/// Operation of a symbolic expression; `set*` copies its operand instead.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BinOp {
+  Add,
+  Mul,
+  And,
+  Or,
+  Gt,
+  Eq,
+}
+
+impl BinOp {
+  fn of(op: Op) -> Option<Self> {
+    match op {
+      Op::Addr | Op::Addi => Some(BinOp::Add),
+      Op::Mulr | Op::Muli => Some(BinOp::Mul),
+      Op::Banr | Op::Bani => Some(BinOp::And),
+      Op::Borr | Op::Bori => Some(BinOp::Or),
+      Op::Setr | Op::Seti => None,
+      Op::Gtir | Op::Gtri | Op::Gtrr => Some(BinOp::Gt),
+      Op::Eqir | Op::Eqri | Op::Eqrr => Some(BinOp::Eq),
+    }
+  }
+
+  // any operation of the family evaluates alike given operand values
+  fn op(self) -> Op {
+    match self {
+      BinOp::Add => Op::Addr,
+      BinOp::Mul => Op::Mulr,
+      BinOp::And => Op::Banr,
+      BinOp::Or => Op::Borr,
+      BinOp::Gt => Op::Gtrr,
+      BinOp::Eq => Op::Eqrr,
+    }
+  }
+}
+
+/// Register value as an expression over the unknown input register.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Sym<W> {
+  Const(W),
+  /// Initial value of the register
+  Input(usize),
+  Bin(BinOp, Rc<Sym<W>>, Rc<Sym<W>>),
+}
+
+impl<W: Word> Sym<W> {
+  /** `a op b` simplified; `None` if constants overflow in `Checked` mode */
+  fn bin(
+    op: BinOp,
+    a: Rc<Self>,
+    b: Rc<Self>,
+    overflow: Overflow,
+  ) -> Option<Rc<Self>> {
+    let zero = Sym::Const(W::ZERO);
+    let one = Sym::Const(W::ONE);
+    let sym = match (op, &*a, &*b) {
+      (_, &Sym::Const(x), &Sym::Const(y)) => {
+        Sym::Const(op.op().eval(x, y, overflow)?)
+      }
+      (BinOp::Add | BinOp::Or, x, y) if *y == zero => x.clone(),
+      (BinOp::Add | BinOp::Or, x, y) if *x == zero => y.clone(),
+      (BinOp::Mul | BinOp::And, x, y) if *x == zero || *y == zero => zero,
+      (BinOp::Mul, x, y) if *y == one => x.clone(),
+      (BinOp::Mul, x, y) if *x == one => y.clone(),
+      (BinOp::Eq, x, y) if x == y => one,
+      (BinOp::Gt, x, y) if x == y => zero,
+      _ => Sym::Bin(op, a, b),
+    };
+    Some(Rc::new(sym))
+  }
+
+  pub fn value(&self) -> Option<W> {
+    match *self {
+      Sym::Const(v) => Some(v),
+      _ => None,
+    }
+  }
+
+  fn has_input(&self) -> bool {
+    match self {
+      Sym::Const(_) => false,
+      Sym::Input(_) => true,
+      Sym::Bin(_, a, b) => a.has_input() || b.has_input(),
+    }
+  }
+
+  /** First comparison depending on the input; branches fork on these */
+  fn condition(self: &Rc<Self>) -> Option<Rc<Self>> {
+    match &**self {
+      Sym::Bin(BinOp::Gt | BinOp::Eq, ..) if self.has_input() => {
+        Some(self.clone())
+      }
+      Sym::Bin(_, a, b) => a.condition().or_else(|| b.condition()),
+      _ => None,
+    }
+  }
+
+  /** Input register `r` and `k` if this is `r + k`, `k + r` or `r` */
+  fn as_offset(&self) -> Option<(usize, W)> {
+    match self {
+      &Sym::Input(r) => Some((r, W::ZERO)),
+      Sym::Bin(BinOp::Add, a, b) => match (&**a, &**b) {
+        (&Sym::Input(r), &Sym::Const(k)) | (&Sym::Const(k), &Sym::Input(r)) => {
+          Some((r, k))
+        }
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  /** This with `from` replaced by `to` and simplified again; a constant
+   * overflowing in `Checked` mode is left unfolded */
+  fn replace(
+    self: &Rc<Self>,
+    from: &Self,
+    to: &Rc<Self>,
+    o: Overflow,
+  ) -> Rc<Self> {
+    if **self == *from {
+      return to.clone();
+    }
+    match &**self {
+      Sym::Bin(op, a, b) => {
+        let (a, b) = (a.replace(from, to, o), b.replace(from, to, o));
+        Self::bin(*op, a.clone(), b.clone(), o)
+          .unwrap_or_else(|| Rc::new(Sym::Bin(*op, a, b)))
+      }
+      _ => self.clone(),
+    }
+  }
+}
+
+/** Write `x` as an operand; parenthesised unless it's atomic */
+fn operand<W: Display>(f: &mut Formatter<'_>, x: &Sym<W>) -> fmt::Result {
+  match x {
+    Sym::Bin(..) => write!(f, "({})", x),
+    _ => write!(f, "{}", x),
+  }
+}
+
+impl<W: Display> Display for Sym<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Sym::Const(v) => write!(f, "{}", v),
+      Sym::Input(r) => write!(f, "r{}", r),
+      Sym::Bin(op, a, b) => {
+        operand(f, a)?;
+        write!(f, " {} ", op.op().symbol().unwrap_or("?"))?;
+        operand(f, b)
+      }
+    }
+  }
+}
+
+/// Condition a path assumes about the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Constraint<W> {
+  pub cond: Rc<Sym<W>>,
+  pub holds: bool,
+}
+
+impl<W: Display> Display for Constraint<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let negated = match &*self.cond {
+      Sym::Bin(BinOp::Eq, a, b) => Some((a, "!=", b)),
+      Sym::Bin(BinOp::Gt, a, b) => Some((a, "<=", b)),
+      _ => None,
+    };
+    match (self.holds, negated) {
+      (true, _) => write!(f, "{}", self.cond),
+      (false, Some((a, sym, b))) => {
+        operand(f, a)?;
+        write!(f, " {} ", sym)?;
+        operand(f, b)
+      }
+      (false, None) => write!(f, "!({})", self.cond),
+    }
+  }
+}
+
+/// How a path ends.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+  Halted,
+  /// Reached a state it was in before, so never halts
+  Loops,
+  /// An instruction can’t execute
+  Trapped,
+  /// Exploration gave up, e.g. on a step limit; says why
+  Unknown(String),
+}
+
+/// Path through a program under constraints on the input.
+#[derive(Clone, Debug)]
+pub struct Path<W> {
+  pub constraints: Vec<Constraint<W>>,
+  pub outcome: Outcome,
+  /// Instructions executed along the path
+  pub steps: u64,
+}
+
+/// Bounds on symbolic exploration.
+#[derive(Copy, Clone, Debug)]
+pub struct Limits {
+  /// Instructions executed along a path
+  pub steps: u64,
+  /// Paths reported, finished or not
+  pub paths: usize,
+}
+
+impl Default for Limits {
+  fn default() -> Self {
+    Limits {
+      steps: 1_000_000,
+      paths: 256,
+    }
+  }
+}
+
+#[derive(Clone)]
+struct State<W, const N: usize> {
+  reg: [Rc<Sym<W>>; N],
+  ip: usize,
+  constraints: Vec<Constraint<W>>,
+  steps: u64,
+  // states at back-edges; loops pass through one
+  seen: HashSet<(usize, [Rc<Sym<W>>; N])>,
+}
+
+impl<W: Word, const N: usize> State<W, N> {
+  fn end(self, outcome: Outcome) -> Path<W> {
+    Path {
+      constraints: self.constraints,
+      outcome,
+      steps: self.steps,
+    }
+  }
+
+  /** Assume `cond` is `holds`; false if earlier assumptions contradict it */
+  fn assume(&mut self, cond: Rc<Sym<W>>, holds: bool, o: Overflow) -> bool {
+    let value = Rc::new(Sym::Const(W::from_bool(holds)));
+    self.reg = self.reg.clone().map(|r| r.replace(&cond, &value, o));
+    // `input == k` pins the input down
+    let pinned = match (&*cond, holds) {
+      (Sym::Bin(BinOp::Eq, a, b), true) => match (&**a, &**b) {
+        (Sym::Input(_), &Sym::Const(_)) => Some((a.clone(), b.clone())),
+        (&Sym::Const(_), Sym::Input(_)) => Some((b.clone(), a.clone())),
+        _ => None,
+      },
+      _ => None,
+    };
+    let known = |c: &Constraint<W>| {
+      let mut known = c.cond.replace(&cond, &value, o);
+      if let Some((input, k)) = &pinned {
+        known = known.replace(input, k, o);
+      }
+      known.value().map(|v| (v != W::ZERO) == c.holds)
+    };
+    if self.constraints.iter().any(|c| known(c) == Some(false)) {
+      return false;
+    }
+    // drop constraints implied by this one
+    self.constraints.retain(|c| known(c).is_none());
+    if let Some((input, k)) = &pinned {
+      self.reg = self.reg.clone().map(|r| r.replace(input, k, o));
+    }
+    self.constraints.push(Constraint { cond, holds });
+    true
+  }
+}
+
+/// Explores paths through the program loaded in `cpu` with register `input`
+/// unknown and the others as they are, from the current IP.
+///
+/// Straight-line code builds expressions over the input; a jump whose target
+/// depends on it forks a path for each outcome of the comparison it depends
+/// on, or for each line when the target is `input + k`.  Paths end on halting,
+/// trapping or revisiting a state, or when `limits` are reached.  Overflow
+/// follows the CPU’s mode; `input + k` jump targets are assumed not to wrap.
+pub fn explore<W: Word, const N: usize>(
+  cpu: &Cpu<W, N>,
+  input: usize,
+  limits: Limits,
+) -> Vec<Path<W>> {
+  let code = cpu.program();
+  let (ip_reg, o) = (cpu.ip_reg(), cpu.overflow());
+  let mut reg = cpu.reg.map(|v| Rc::new(Sym::Const(v)));
+  if let Some(r) = reg.get_mut(input) {
+    *r = Rc::new(Sym::Input(input));
+  }
+  let mut pending = vec![State {
+    reg,
+    ip: cpu.ip(),
+    constraints: Vec::new(),
+    steps: 0,
+    seen: HashSet::new(),
+  }];
+  let mut paths = Vec::new();
+  while let Some(mut s) = pending.pop() {
+    if paths.len() + pending.len() >= limits.paths {
+      paths.push(s.end(Outcome::Unknown("path limit reached".into())));
+      continue;
+    }
+    let path = loop {
+      let i = match code.get(s.ip) {
+        Some(&i) => i,
+        None => break Some(s.end(Outcome::Halted)),
+      };
+      if s.steps == limits.steps {
+        break Some(s.end(Outcome::Unknown("step limit reached".into())));
+      }
+      s.steps += 1;
+      if let Some(r) = ip_reg {
+        s.reg[r] = Rc::new(Sym::Const(W::from_usize(s.ip)));
+      }
+      let operand = |x: W, is_reg: bool| match is_reg {
+        true => s.reg.get(x.to_usize()).cloned(),
+        false => Some(Rc::new(Sym::Const(x))),
+      };
+      let (a, b, c) = match (
+        operand(i.a, i.op.reads_a()),
+        operand(i.b, i.op.reads_b()),
+        i.c.to_usize(),
+      ) {
+        (Some(a), Some(b), c) if c < N => (a, b, c),
+        _ => break Some(s.end(Outcome::Trapped)),
+      };
+      s.reg[c] = match BinOp::of(i.op) {
+        Some(op) => match Sym::bin(op, a, b, o) {
+          Some(v) => v,
+          None => break Some(s.end(Outcome::Trapped)),
+        },
+        None => a,
+      };
+      let from = s.ip;
+      let target = match ip_reg {
+        Some(r) => s.reg[r].clone(),
+        None => Rc::new(Sym::Const(W::from_usize(from))),
+      };
+      if let Some(t) = target.value() {
+        s.ip = t.to_usize().saturating_add(1);
+        if s.ip <= from && !s.seen.insert((s.ip, s.reg.clone())) {
+          break Some(s.end(Outcome::Loops));
+        }
+        continue;
+      }
+      // fork on the jump's dependence on the input
+      if let Some(cond) = target.condition() {
+        for holds in [false, true] {
+          let mut fork = s.clone();
+          if fork.assume(cond.clone(), holds, o) {
+            // jump is resolved or forks again on rerunning the instruction
+            fork.ip = from;
+            fork.steps -= 1;
+            pending.push(fork);
+          }
+        }
+        break None;
+      }
+      if let Some((r, k)) = target.as_offset() {
+        // target is past the program whatever the input
+        let k = k.to_usize();
+        if k >= code.len() {
+          break Some(s.end(Outcome::Halted));
+        }
+        let input = Rc::new(Sym::Input(r));
+        let at = |line: usize| Rc::new(Sym::Const(W::from_usize(line - 1 - k)));
+        // inputs from the one jumping past the last line on halt
+        let past = Rc::new(Sym::Bin(BinOp::Gt, at(code.len()), input.clone()));
+        let mut fork = s.clone();
+        if fork.assume(past, false, o) {
+          fork.ip = code.len();
+          paths.push(fork.end(Outcome::Halted));
+        }
+        for line in (k + 1..code.len()).rev() {
+          let mut fork = s.clone();
+          let cond = Rc::new(Sym::Bin(BinOp::Eq, input.clone(), at(line)));
+          if fork.assume(cond, true, o) {
+            fork.ip = from;
+            fork.steps -= 1;
+            pending.push(fork);
+          }
+        }
+        break None;
+      }
+      break Some(s.end(Outcome::Unknown(format!("jump to ({}) + 1", target))));
+    };
+    paths.extend(path);
+  }
+  paths
+}
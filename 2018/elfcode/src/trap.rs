@@ -0,0 +1,34 @@
+use crate::Instruction;
+use std::{
+  error::Error,
+  fmt::{self, Debug, Display, Formatter},
+};
+
+/// Why an instruction couldn’t execute.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TrapKind {
+  /// Result of `add*` or `mul*` doesn’t fit a word in `Checked` mode
+  Overflow,
+  /// Register operand isn’t one of the CPU’s
+  InvalidRegister(usize),
+}
+
+/// Instruction that trapped and where; the CPU is left at it, unexecuted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Trap<W> {
+  pub kind: TrapKind,
+  pub ip: usize,
+  pub instruction: Instruction<W>,
+}
+
+impl<W: Display> Display for Trap<W> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self.kind {
+      TrapKind::Overflow => write!(f, "Overflow")?,
+      TrapKind::InvalidRegister(r) => write!(f, "Invalid register {}", r)?,
+    }
+    write!(f, " in `{}` at ip {}", self.instruction, self.ip)
+  }
+}
+
+impl<W: Debug + Display> Error for Trap<W> {}
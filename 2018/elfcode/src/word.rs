@@ -0,0 +1,153 @@
+use std::{
+  error::Error,
+  fmt::{self, Debug, Display, Formatter},
+  hash::Hash,
+  ops::{BitAnd, BitOr},
+  str::FromStr,
+};
+
+/// Register and operand type of a `Cpu`; implemented for unsigned integers.
+///
+/// Addition and multiplication go through `Overflow` rather than `+` and `*`
+/// so that debug and release builds agree.
+pub trait Word:
+  Copy
+  + Default
+  + Debug
+  + Display
+  + Hash
+  + Ord
+  + FromStr
+  + BitAnd<Output = Self>
+  + BitOr<Output = Self>
+{
+  const ZERO: Self;
+  const ONE: Self;
+
+  /// Truncating conversion; used to write the IP into its bound register.
+  fn from_usize(n: usize) -> Self;
+
+  /// Saturating conversion; used for register indices and IP reads.
+  fn to_usize(self) -> usize;
+
+  fn wrapping_add(self, rhs: Self) -> Self;
+  fn wrapping_mul(self, rhs: Self) -> Self;
+  fn saturating_add(self, rhs: Self) -> Self;
+  fn saturating_mul(self, rhs: Self) -> Self;
+  fn checked_add(self, rhs: Self) -> Option<Self>;
+  fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+  fn from_bool(b: bool) -> Self {
+    match b {
+      true => Self::ONE,
+      false => Self::ZERO,
+    }
+  }
+}
+
+macro_rules! impl_word {
+  ($($t:ty),*) => {
+    $(
+      impl Word for $t {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+
+        fn from_usize(n: usize) -> Self {
+          n as $t
+        }
+
+        fn to_usize(self) -> usize {
+          usize::try_from(self).unwrap_or(usize::MAX)
+        }
+
+        fn wrapping_add(self, rhs: Self) -> Self {
+          <$t>::wrapping_add(self, rhs)
+        }
+
+        fn wrapping_mul(self, rhs: Self) -> Self {
+          <$t>::wrapping_mul(self, rhs)
+        }
+
+        fn saturating_add(self, rhs: Self) -> Self {
+          <$t>::saturating_add(self, rhs)
+        }
+
+        fn saturating_mul(self, rhs: Self) -> Self {
+          <$t>::saturating_mul(self, rhs)
+        }
+
+        fn checked_add(self, rhs: Self) -> Option<Self> {
+          <$t>::checked_add(self, rhs)
+        }
+
+        fn checked_mul(self, rhs: Self) -> Option<Self> {
+          <$t>::checked_mul(self, rhs)
+        }
+      }
+    )*
+  };
+}
+
+impl_word!(u8, u16, u32, u64, usize);
+
+/// What `addr`, `addi`, `mulr` and `muli` do when a result doesn’t fit a word.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+  /// Wrap around modulo the word size
+  #[default]
+  Wrapping,
+  /// Clamp to the largest word
+  Saturating,
+  /// Trap on the instruction
+  Checked,
+}
+
+impl Overflow {
+  /** Sum of `a` and `b`; `None` if it overflows in `Checked` mode */
+  pub fn add<W: Word>(self, a: W, b: W) -> Option<W> {
+    match self {
+      Overflow::Wrapping => Some(a.wrapping_add(b)),
+      Overflow::Saturating => Some(a.saturating_add(b)),
+      Overflow::Checked => a.checked_add(b),
+    }
+  }
+
+  /** Product of `a` and `b`; `None` if it overflows in `Checked` mode */
+  pub fn mul<W: Word>(self, a: W, b: W) -> Option<W> {
+    match self {
+      Overflow::Wrapping => Some(a.wrapping_mul(b)),
+      Overflow::Saturating => Some(a.saturating_mul(b)),
+      Overflow::Checked => a.checked_mul(b),
+    }
+  }
+}
+
+impl Display for Overflow {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Overflow::Wrapping => "wrapping",
+      Overflow::Saturating => "saturating",
+      Overflow::Checked => "checked",
+    })
+  }
+}
+
+impl FromStr for Overflow {
+  type Err = Box<dyn Error>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "wrapping" => Ok(Overflow::Wrapping),
+      "saturating" => Ok(Overflow::Saturating),
+      "checked" => Ok(Overflow::Checked),
+      _ => Err(
+        format!(
+          "Unrecognized overflow mode: {}; expected wrapping, saturating or \
+           checked",
+          s
+        )
+        .into(),
+      ),
+    }
+  }
+}
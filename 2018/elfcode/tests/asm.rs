@@ -0,0 +1,73 @@
+use elfcode::{assemble, Cpu, Program};
+
+fn error(source: &str) -> String {
+  match assemble::<u16>(source) {
+    Ok(p) => panic!("assembled {:?}", p),
+    Err(e) => e.to_string(),
+  }
+}
+
+fn run(program: Program<u64>, r0: u64) -> [u64; 4] {
+  let mut cpu = Cpu::<u64, 4>::new();
+  cpu.load(program).unwrap();
+  cpu.reg[0] = r0;
+  cpu.run().unwrap();
+  cpu.reg
+}
+
+#[test]
+fn immediate_out_of_range() {
+  assert_eq!(
+    error("seti 1 0 0\nseti 70000 0 0"),
+    "Value 70000 doesn't fit in a word at line 2"
+  );
+  assert_eq!(
+    error(".const BIG 65536\nseti BIG 0 0"),
+    "Value 65536 doesn't fit in a word at line 2"
+  );
+  assert!(assemble::<u16>("seti 65535 0 0").is_ok());
+}
+
+#[test]
+fn forward_label_references() {
+  let program = assemble::<u64>(
+    ".ip r3\n.const END done+1\njmp skip\nseti 7 0 0\n\
+     skip: seti END 0 1\ndone: halt",
+  )
+  .unwrap();
+  // `skip` is line 2 and `done` line 3, so `jmp skip` writes 1 to the IP
+  assert_eq!(program.instructions[0].a, 1);
+  assert_eq!(program.instructions[2].a, 4);
+  assert_eq!(run(program, 0)[..2], [0, 4]);
+}
+
+#[test]
+fn constants_going_round() {
+  assert_eq!(
+    error(".const A B\n.const B A\nseti A 0 0"),
+    "Circular constant definitions through `B` at line 1"
+  );
+}
+
+#[test]
+fn conditional_jumps() {
+  // r1 counts how many of the two jumps were taken
+  let source = "\
+.ip r3
+        jt r0 yes
+        jmp no
+yes:    addi r1 1 r1
+no:     jf r0 zero
+        halt
+zero:   addi r1 1 r1
+";
+  let program = assemble::<u64>(source).unwrap();
+  let text = program.to_string();
+  assert_eq!(
+    text,
+    "#ip 3\naddr 0 3 3\naddi 3 1 3\nseti 3 0 3\nseti 4 0 3\naddi 1 1 1\n\
+     addr 0 3 3\nseti 7 0 3\nseti 8 0 3\naddi 1 1 1\n"
+  );
+  assert_eq!(run(program.clone(), 1)[1], 1);
+  assert_eq!(run(program, 0)[1], 1);
+}
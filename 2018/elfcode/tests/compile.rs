@@ -0,0 +1,102 @@
+use elfcode::{Compiled, Cpu, Overflow, Program};
+
+const DAY_19: &str = include_str!("../../day_19/input/input");
+const DAY_21: &str = include_str!("../../day_21/input/input");
+
+/** Run `source` with R0 = `r0` both interpreted and compiled, comparing the
+ * CPUs each time line `stop` is reached, `visits` times, or at the halt */
+fn compare(source: &str, r0: u64, stop: Option<usize>, visits: usize) {
+  let program = source.parse::<Program<u64>>().unwrap();
+  let mut plain = Cpu::<u64, 6>::new();
+  plain.load(program.clone()).unwrap();
+  plain.reg[0] = r0;
+  let mut fast = Cpu::<u64, 6>::new();
+  fast.load(program).unwrap();
+  fast.reg[0] = r0;
+  let stops = stop.into_iter().collect::<Vec<_>>();
+  let compiled = Compiled::new(&fast, &stops).unwrap();
+  for visit in 0..visits {
+    let count = compiled.run(&mut fast).unwrap();
+    let mut steps = 0;
+    while plain.step().unwrap() {
+      steps += 1;
+      if Some(plain.ip()) == stop {
+        break;
+      }
+    }
+    assert_eq!(
+      (fast.ip(), fast.reg, count),
+      (plain.ip(), plain.reg, steps),
+      "visit {}",
+      visit
+    );
+    if plain.is_halted() {
+      return;
+    }
+  }
+}
+
+#[test]
+fn day_19_to_halt() {
+  compare(DAY_19, 0, None, 1);
+}
+
+#[test]
+fn day_19_inner_loop() {
+  compare(DAY_19, 1, Some(8), 2000);
+}
+
+#[test]
+fn day_21_comparisons() {
+  compare(DAY_21, 0, Some(28), 50);
+}
+
+#[test]
+fn day_21_to_halt() {
+  compare(DAY_21, 9079325, None, 1);
+}
+
+// r1 overflows at line 3, in the block from line 0 after a constant jump
+const OVERFLOWS: &str = "#ip 5
+seti 18446744073709551615 0 1
+seti 2 0 5
+seti 7 0 0
+addi 1 1 1
+addi 2 1 2";
+
+/** CPU with `OVERFLOWS` loaded, in overflow `mode` */
+fn overflows(mode: Overflow) -> Cpu<u64, 6> {
+  let mut cpu = Cpu::new();
+  cpu.load(OVERFLOWS.parse().unwrap()).unwrap();
+  cpu.set_overflow(mode);
+  cpu
+}
+
+#[test]
+fn trap_mid_block() {
+  let mut plain = overflows(Overflow::Checked);
+  let mut fast = overflows(Overflow::Checked);
+  let compiled = Compiled::new(&fast, &[]).unwrap();
+  let expected = plain.run().unwrap_err();
+  let trap = compiled.run(&mut fast).unwrap_err();
+  assert_eq!((trap.ip, trap.kind), (3, expected.kind));
+  assert_eq!(expected.ip, 3);
+  assert_eq!((fast.ip(), fast.reg), (plain.ip(), plain.reg));
+}
+
+#[test]
+fn count_past_mode_change_mid_block() {
+  // compiled to trap at line 3, but the CPU now wraps, so the interpreter
+  // runs the line and the count must take in the block's lines before it
+  let mut fast = overflows(Overflow::Checked);
+  let compiled = Compiled::new(&fast, &[]).unwrap();
+  fast.set_overflow(Overflow::Wrapping);
+  let mut plain = overflows(Overflow::Wrapping);
+  let mut steps = 0;
+  while plain.step().unwrap() {
+    steps += 1;
+  }
+  let count = compiled.run(&mut fast).unwrap();
+  assert_eq!(steps, 4);
+  assert_eq!((fast.ip(), fast.reg, count), (plain.ip(), plain.reg, steps));
+}
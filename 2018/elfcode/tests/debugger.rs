@@ -0,0 +1,160 @@
+use elfcode::{Cpu, Debugger};
+
+// counts r0 up from 0 on line 1, halting once it's over 2
+const COUNT: &str = "#ip 3
+seti 0 0 2
+addi 0 1 0
+gtri 0 2 1
+addr 1 3 3
+seti 0 0 3";
+
+fn debugger() -> Debugger<u64, 4> {
+  let mut cpu = Cpu::new();
+  cpu.load(COUNT.parse().unwrap()).unwrap();
+  Debugger::new(cpu)
+}
+
+/** Output of running `commands` one by one */
+fn run(debugger: &mut Debugger<u64, 4>, commands: &[&str]) -> String {
+  let mut out = Vec::new();
+  for command in commands {
+    assert!(debugger.command(command, &mut out).unwrap(), "{}", command);
+  }
+  String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn step_and_back() {
+  let mut d = debugger();
+  assert_eq!(
+    run(&mut d, &["s 3"]),
+    "r0=1 r1=0 r2=0 r3(ip)=2 | ip=3 steps=3\n  3: addr 1 3 3\n"
+  );
+  run(&mut d, &["s 4"]);
+  assert_eq!((d.cpu.ip(), d.cpu.reg), (3, [2, 0, 0, 2]));
+  assert_eq!(
+    run(&mut d, &["rs 2"]),
+    "r0=1 r1=0 r2=0 r3(ip)=0 | ip=1 steps=5\n  1: addi 0 1 0\n"
+  );
+  let out = run(&mut d, &["rs 10"]);
+  assert!(
+    out.starts_with("History exhausted after 5 steps back\n"),
+    "{}",
+    out
+  );
+  assert_eq!((d.cpu.ip(), d.cpu.reg), (0, [0; 4]));
+}
+
+#[test]
+fn breakpoints() {
+  let mut d = debugger();
+  assert_eq!(
+    run(&mut d, &["b 4", "break 1", "l"]),
+    "Breakpoint at 1\nBreakpoint at 4\n"
+  );
+  let out = run(&mut d, &["c"]);
+  assert!(out.starts_with("Breakpoint at 1\n"), "{}", out);
+  assert_eq!((d.cpu.ip(), d.cpu.reg[0]), (1, 0));
+  run(&mut d, &["c"]);
+  assert_eq!((d.cpu.ip(), d.cpu.reg[0]), (4, 1));
+  assert_eq!(run(&mut d, &["d 1", "d 1"]), "No breakpoint at 1\n");
+  run(&mut d, &["c"]);
+  assert_eq!((d.cpu.ip(), d.cpu.reg[0]), (4, 2));
+  let out = run(&mut d, &["c"]);
+  assert!(out.starts_with("Program halted\n"), "{}", out);
+  assert!(d.cpu.is_halted());
+  assert_eq!(d.cpu.reg[0], 3);
+}
+
+#[test]
+fn watches() {
+  let mut d = debugger();
+  run(&mut d, &["w r0 == 2", "w r1"]);
+  assert_eq!(
+    run(&mut d, &["l"]),
+    "Watch 0: r0 == 2\nWatch 1: r1 changes\n"
+  );
+  let out = run(&mut d, &["c"]);
+  assert!(out.starts_with("Watch 0: r0 == 2\n"), "{}", out);
+  assert_eq!((d.cpu.ip(), d.cpu.reg[0]), (2, 2));
+  let out = run(&mut d, &["c"]);
+  assert!(out.starts_with("Watch 1: r1 changes\n"), "{}", out);
+  assert_eq!((d.cpu.ip(), &d.cpu.reg[..2]), (3, &[3, 1][..]));
+  assert_eq!(
+    run(&mut d, &["u 0", "u 1", "l"]),
+    "No watch numbered 1\nWatch 0: r1 changes\n"
+  );
+}
+
+#[test]
+fn snapshots_and_registers() {
+  let mut d = debugger();
+  run(&mut d, &["s 2", "snap", "set r0 7", "s"]);
+  assert_eq!(d.cpu.reg[..2], [7, 1]);
+  run(&mut d, &["restore 0"]);
+  assert_eq!((d.cpu.ip(), &d.cpu.reg[..2]), (2, &[1, 0][..]));
+  assert_eq!(
+    run(&mut d, &["p", "i"]),
+    "r0=1 r1=0 r2=0 r3(ip)=1 | ip=2 steps=2\n  2: gtri 0 2 1\n"
+  );
+  run(&mut d, &["r"]);
+  assert_eq!((d.cpu.ip(), d.cpu.reg), (0, [0; 4]));
+}
+
+#[test]
+fn invalid_commands() {
+  let mut d = debugger();
+  let errors = run(
+    &mut d,
+    &[
+      "s x",
+      "s -1",
+      "rs 2.5",
+      "b",
+      "d one",
+      "w 0",
+      "w r4",
+      "w r0 = 1",
+      "w r0 ==",
+      "u",
+      "restore 0",
+      "set r1",
+      "frob 1",
+    ],
+  );
+  assert_eq!(
+    errors.lines().collect::<Vec<_>>(),
+    [
+      "invalid digit found in string",
+      "invalid digit found in string",
+      "invalid digit found in string",
+      "Expected a number",
+      "Expected a number",
+      "Expected a register in [r0, r3]",
+      "Expected a register in [r0, r3]",
+      "Expected `==` after register",
+      "Expected a number",
+      "Expected a number",
+      "No snapshot numbered 0",
+      "Expected a number",
+      "Unknown command `frob`; try `h`",
+    ]
+  );
+  // none of them did anything
+  assert_eq!((d.cpu.ip(), d.cpu.reg), (0, [0; 4]));
+  assert_eq!(run(&mut d, &["l"]), "");
+  assert!(!d.command("q", &mut Vec::new()).unwrap());
+}
+
+#[test]
+fn repl_repeats_last_command() {
+  let mut d = debugger();
+  let mut out = Vec::new();
+  d.repl("s 2\n\n  \ns\nq\ns\n".as_bytes(), &mut out).unwrap();
+  // the blank lines each step twice more, and nothing runs after quitting
+  assert_eq!((d.cpu.ip(), d.cpu.reg[0]), (3, 2));
+  assert_eq!(
+    String::from_utf8(out).unwrap().matches("(elfdbg) ").count(),
+    5
+  );
+}
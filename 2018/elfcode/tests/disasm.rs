@@ -0,0 +1,46 @@
+use elfcode::{disassemble, Program};
+
+fn disasm(source: &str) -> String {
+  disassemble(&source.parse::<Program<u64>>().unwrap())
+}
+
+#[test]
+fn day_19_golden() {
+  assert_eq!(
+    disasm(include_str!("../../day_19/input/input")),
+    include_str!("disasm/day_19.txt")
+  );
+}
+
+#[test]
+fn day_21_golden() {
+  assert_eq!(
+    disasm(include_str!("../../day_21/input/input")),
+    include_str!("disasm/day_21.txt")
+  );
+}
+
+#[test]
+fn comparison_folds_into_condition() {
+  let out = disasm("#ip 4\ngtrr 2 5 3\naddr 3 4 4\nseti 0 0 0\nseti 0 0 0");
+  assert!(out.contains("if r2 > r5 goto L3"), "{}", out);
+}
+
+#[test]
+fn overwritten_operand_tests_flag() {
+  // `r3 == r5` is no longer what r3 holds after the comparison
+  let out = disasm("#ip 4\neqrr 3 5 3\naddr 3 4 4\nseti 0 0 0\nseti 0 0 0");
+  assert!(out.contains("if r3 goto L3"), "{}", out);
+  let out = disasm("#ip 3\neqri 4 72 4\naddr 4 3 3\nseti 0 0 0");
+  assert!(out.contains("if r4 halt"), "{}", out);
+}
+
+#[test]
+fn relative_jump_at_top_of_word_range() {
+  let out = disasm("#ip 0\naddi 1 18446744073709551615 0");
+  assert!(
+    out.contains("goto (r1 + 18446744073709551615) + 1"),
+    "{}",
+    out
+  );
+}
@@ -0,0 +1,126 @@
+use elfcode::{assemble, first_halting_value, halting_values, Cpu, Halt};
+
+// counts up by 3 from 0, halting once the count equals r0 and starting over
+// past 11, so 3, 6, 9 and 12 halt it
+const COUNTER: &str = "
+.ip r5
+.reg input r0
+.reg n r1
+.reg t r2
+        seti 0 0 n
+loop:   addi n 3 n
+        eqrr n input t
+        jt t done
+        gtri n 11 t
+        jf t loop
+        seti 0 0 n
+        jmp loop
+done:   halt";
+
+fn load(source: &str) -> Cpu<u64, 6> {
+  let mut cpu = Cpu::new();
+  cpu.load(assemble(source).unwrap()).unwrap();
+  cpu
+}
+
+/** Instructions `source` runs till it halts with r0 = `value` */
+fn steps(source: &str, value: u64) -> u64 {
+  let mut cpu = load(source);
+  cpu.reg[0] = value;
+  let mut steps = 0;
+  while cpu.step().unwrap() {
+    steps += 1;
+    assert!(steps < 10_000, "{} doesn't halt", value);
+  }
+  steps
+}
+
+#[test]
+fn counter_values() {
+  let halts = halting_values(&mut load(COUNTER), 0).unwrap();
+  let expected = [3, 6, 9, 12].map(|value| Halt {
+    value,
+    line: 2,
+    steps: steps(COUNTER, value),
+  });
+  assert_eq!(halts, expected);
+  assert!(halts.windows(2).all(|w| w[0].steps < w[1].steps));
+}
+
+#[test]
+fn counter_first_value() {
+  let first = first_halting_value(&mut load(COUNTER), 0).unwrap();
+  assert_eq!(first.map(|halt| halt.value), Some(3));
+}
+
+#[test]
+fn cycle_without_halts() {
+  // a match loops for good, so no value halts; the scan ends on seeing the
+  // comparison again in the same state
+  let source = "
+.ip r5
+        seti 0 0 1
+loop:   eqri 0 7 2
+        jt r2 stuck
+        jmp loop
+stuck:  jmp stuck";
+  let mut cpu = load(source);
+  assert_eq!(halting_values(&mut cpu, 0).unwrap(), []);
+  assert!(!cpu.is_halted());
+  assert_eq!(cpu.ip(), 1);
+}
+
+/** Program halting when r0 is 5, after a loop of `rounds` rounds */
+fn slow_halt(rounds: u64) -> String {
+  format!(
+    "
+.ip r5
+        seti 0 0 1
+check:  eqri 0 5 2
+        jt r2 count
+        jmp check
+count:  addi 1 1 1
+        gtri 1 {} 2
+        jf r2 count
+        halt",
+    rounds - 1
+  )
+}
+
+#[test]
+fn halt_within_probe() {
+  // each round is 4 instructions; 10 of them end within `PROBE_STEPS`
+  let source = slow_halt(10);
+  let halts = halting_values(&mut load(&source), 0).unwrap();
+  let steps = steps(&source, 5);
+  assert_eq!(
+    halts,
+    [Halt {
+      value: 5,
+      line: 1,
+      steps
+    }]
+  );
+}
+
+#[test]
+fn halt_beyond_probe_is_missed() {
+  // a taken comparison runs at most `PROBE_STEPS` (64) instructions looking
+  // for the end; 20 rounds take longer, so 5 isn't found to halt
+  let source = slow_halt(20);
+  assert!(steps(&source, 5) > 64);
+  assert_eq!(halting_values(&mut load(&source), 0).unwrap(), []);
+}
+
+#[test]
+fn input_used_otherwise() {
+  let mut cpu = load(".ip r5\naddi 0 1 1\nhalt");
+  let err = halting_values(&mut cpu, 0).unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "Register 0 read other than by an equality comparison at line 0"
+  );
+  let mut cpu = load(".ip r5\nseti 1 0 0\nhalt");
+  let err = halting_values(&mut cpu, 0).unwrap_err();
+  assert_eq!(err.to_string(), "Register 0 written at line 0");
+}
@@ -0,0 +1,72 @@
+use elfcode::{Cpu, Overflow, TrapKind};
+
+/** CPU with `source` loaded, logging history */
+fn load(source: &str) -> Cpu<u64, 4> {
+  let mut cpu = Cpu::new();
+  cpu.load(source.parse().unwrap()).unwrap();
+  cpu.enable_history(16);
+  cpu
+}
+
+#[test]
+fn restore_snapshot() {
+  let mut cpu = load("#ip 3\naddi 0 1 0\nseti 0 0 3");
+  cpu.set_overflow(Overflow::Saturating);
+  cpu.step().unwrap();
+  let snapshot = cpu.snapshot();
+  cpu.set_overflow(Overflow::Checked);
+  cpu.reg = [7, 8, 9, 10];
+  cpu.set_ip(0);
+  cpu.restore(&snapshot);
+  assert_eq!(cpu.snapshot(), snapshot);
+  assert_eq!(cpu.overflow(), Overflow::Saturating);
+  assert_eq!((cpu.ip(), cpu.reg), (1, [1, 0, 0, 0]));
+  // history before the snapshot no longer leads here
+  assert!(!cpu.step_back());
+}
+
+#[test]
+fn step_back_over_jump() {
+  // line 2 jumps back to line 1
+  let mut cpu = load("#ip 3\nseti 5 0 1\naddi 0 1 0\nseti 0 0 3");
+  let states = (0..5)
+    .map(|_| {
+      let state = cpu.snapshot();
+      assert!(cpu.step().unwrap());
+      state
+    })
+    .collect::<Vec<_>>();
+  // the last step is the jump
+  assert_eq!((cpu.ip(), cpu.reg[0]), (1, 2));
+  for state in states.iter().rev() {
+    assert!(cpu.step_back());
+    assert_eq!(cpu.snapshot(), *state);
+  }
+  assert!(!cpu.step_back());
+  assert_eq!((cpu.ip(), cpu.reg), (0, [0; 4]));
+}
+
+#[test]
+fn step_back_over_trap() {
+  // r0 overflows on line 1 in `Checked` mode
+  let mut cpu = load("#ip 3\nseti 18446744073709551615 0 0\naddi 0 1 0");
+  cpu.set_overflow(Overflow::Checked);
+  cpu.step().unwrap();
+  let before = cpu.snapshot();
+  let trap = cpu.step().unwrap_err();
+  assert_eq!((trap.kind, trap.ip), (TrapKind::Overflow, 1));
+  // a trap isn't logged; back goes to before line 0
+  assert!(cpu.step_back());
+  assert_eq!((cpu.ip(), cpu.reg), (0, [0; 4]));
+  cpu.step().unwrap();
+  assert_eq!(cpu.snapshot(), before);
+  // wrapping gets past; stepping back returns to the mode the step ran in
+  cpu.set_overflow(Overflow::Wrapping);
+  assert!(cpu.step().unwrap());
+  assert_eq!(cpu.reg[0], 0);
+  cpu.set_overflow(Overflow::Checked);
+  assert!(cpu.step_back());
+  assert_eq!(cpu.overflow(), Overflow::Wrapping);
+  assert_eq!((cpu.ip(), cpu.reg), (before.ip, before.reg));
+  assert!(cpu.step().unwrap());
+}
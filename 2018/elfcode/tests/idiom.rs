@@ -0,0 +1,51 @@
+use elfcode::{assemble, Cpu, Idiom, IdiomKind, Program};
+
+fn sample() -> Program<u64> {
+  assemble(include_str!("../samples/divisor_sum.asm")).unwrap()
+}
+
+#[test]
+fn finds_relocated_loop() {
+  let program = sample();
+  let idioms = Idiom::find(&program.instructions, program.ip_reg);
+  assert_eq!(idioms.len(), 1);
+  assert_eq!(idioms[0].kind, IdiomKind::DivisorSum);
+  assert_eq!((idioms[0].entry, idioms[0].exit), (4, 19));
+  assert_eq!(
+    idioms[0].to_string(),
+    "[4, 19): r5 += sum of divisors of r4"
+  );
+}
+
+#[test]
+fn closed_form_matches_interpreter() {
+  let program = sample();
+  let idioms = Idiom::find(&program.instructions, program.ip_reg);
+  let mut plain = Cpu::<u64, 6>::new();
+  plain.load(program.clone()).unwrap();
+  plain.run().unwrap();
+  let mut fast = Cpu::<u64, 6>::new();
+  fast.load(program).unwrap();
+  fast.run_with(&idioms).unwrap();
+  assert_eq!(plain.reg[5], 1920);
+  assert_eq!(fast.reg, plain.reg);
+}
+
+#[test]
+fn finds_day_19_loop() {
+  let program = include_str!("../../day_19/input/input")
+    .parse::<Program<u64>>()
+    .unwrap();
+  let idioms = Idiom::find(&program.instructions, program.ip_reg);
+  assert_eq!(idioms.len(), 1);
+  assert_eq!((idioms[0].entry, idioms[0].exit), (1, 16));
+}
+
+#[test]
+fn ignores_broken_loop() {
+  // inner counter steps by 2, which the closed form doesn't model
+  let program = sample();
+  let mut code = program.instructions.clone();
+  code[11].b = 2;
+  assert!(Idiom::find(&code, program.ip_reg).is_empty());
+}
@@ -0,0 +1,82 @@
+use elfcode::{Cpu, Overflow, Program, TrapKind};
+
+/** R0 after running `source` on bytes in overflow `mode`, or the trap */
+fn run(source: &str, mode: Overflow) -> Result<u8, TrapKind> {
+  let mut cpu = Cpu::<u8, 4>::new();
+  cpu.load(source.parse::<Program<u8>>().unwrap()).unwrap();
+  cpu.set_overflow(mode);
+  cpu.run().map_err(|trap| trap.kind)?;
+  Ok(cpu.reg[0])
+}
+
+// R0 is 255 then an operation at the edge of a byte
+const ADD: &str = "seti 255 0 0\naddi 0 1 0";
+const ADD_FITS: &str = "seti 254 0 0\naddi 0 1 0";
+const ADDR: &str = "seti 255 0 0\nseti 2 0 1\naddr 0 1 0";
+const MUL: &str = "seti 16 0 0\nmuli 0 16 0";
+const MUL_FITS: &str = "seti 15 0 0\nmuli 0 17 0";
+const MULR: &str = "seti 255 0 0\nseti 2 0 1\nmulr 0 1 0";
+
+#[test]
+fn wrapping() {
+  let mode = Overflow::Wrapping;
+  assert_eq!(run(ADD, mode), Ok(0));
+  assert_eq!(run(ADD_FITS, mode), Ok(255));
+  assert_eq!(run(ADDR, mode), Ok(1));
+  assert_eq!(run(MUL, mode), Ok(0));
+  assert_eq!(run(MUL_FITS, mode), Ok(255));
+  assert_eq!(run(MULR, mode), Ok(254));
+}
+
+#[test]
+fn saturating() {
+  let mode = Overflow::Saturating;
+  assert_eq!(run(ADD, mode), Ok(255));
+  assert_eq!(run(ADD_FITS, mode), Ok(255));
+  assert_eq!(run(ADDR, mode), Ok(255));
+  assert_eq!(run(MUL, mode), Ok(255));
+  assert_eq!(run(MUL_FITS, mode), Ok(255));
+  assert_eq!(run(MULR, mode), Ok(255));
+}
+
+#[test]
+fn checked() {
+  let mode = Overflow::Checked;
+  assert_eq!(run(ADD, mode), Err(TrapKind::Overflow));
+  assert_eq!(run(ADD_FITS, mode), Ok(255));
+  assert_eq!(run(ADDR, mode), Err(TrapKind::Overflow));
+  assert_eq!(run(MUL, mode), Err(TrapKind::Overflow));
+  assert_eq!(run(MUL_FITS, mode), Ok(255));
+  assert_eq!(run(MULR, mode), Err(TrapKind::Overflow));
+}
+
+#[test]
+fn checked_trap_state() {
+  // IP bound to R3; the trap leaves the CPU at line 2 with R0 unchanged
+  let source = "#ip 3\nseti 200 0 0\nseti 100 0 1\naddr 0 1 0\nseti 0 0 2";
+  let mut cpu = Cpu::<u8, 4>::new();
+  cpu.load(source.parse().unwrap()).unwrap();
+  cpu.set_overflow(Overflow::Checked);
+  let trap = cpu.run().unwrap_err();
+  assert_eq!((trap.kind, trap.ip), (TrapKind::Overflow, 2));
+  assert_eq!(trap.to_string(), "Overflow in `addr 0 1 0` at ip 2");
+  assert_eq!(cpu.ip(), 2);
+  assert_eq!(cpu.reg, [200, 100, 0, 2]);
+  // running on in another mode carries on from the trapping line
+  cpu.set_overflow(Overflow::Saturating);
+  cpu.run().unwrap();
+  assert_eq!(cpu.reg, [255, 100, 0, 3]);
+}
+
+#[test]
+fn u64_boundary() {
+  let mut cpu = Cpu::<u64, 4>::new();
+  let source = "seti 18446744073709551615 0 0\naddi 0 1 1\nmuli 0 2 2";
+  cpu.load(source.parse().unwrap()).unwrap();
+  cpu.run().unwrap();
+  assert_eq!(cpu.reg[1..3], [0, u64::MAX - 1]);
+  cpu.load(source.parse().unwrap()).unwrap();
+  cpu.set_overflow(Overflow::Saturating);
+  cpu.run().unwrap();
+  assert_eq!(cpu.reg[1..3], [u64::MAX; 2]);
+}
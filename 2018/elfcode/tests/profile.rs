@@ -0,0 +1,40 @@
+use elfcode::{Cpu, Loop, Profile};
+
+#[test]
+fn ignores_lines_past_end() {
+  let mut profile = Profile::new(3);
+  profile.record(1, 2);
+  profile.record(2, 0);
+  // neither the hit nor the back edge from line 5 counts
+  profile.record(5, 1);
+  assert_eq!(profile.hits(), [0, 1, 1]);
+  assert_eq!(
+    profile.hot_loops(),
+    [Loop {
+      start: 0,
+      end: 2,
+      iterations: 1,
+      hits: 2,
+    }]
+  );
+}
+
+#[test]
+fn loops_of_day_19() {
+  let mut cpu = Cpu::<u64, 6>::new();
+  cpu
+    .load(include_str!("../../day_19/input/input").parse().unwrap())
+    .unwrap();
+  cpu.enable_profiling();
+  cpu.run().unwrap();
+  let profile = cpu.take_profile().unwrap();
+  assert_eq!(profile.total(), profile.hits().iter().sum::<u64>());
+  // nested loops over divisor candidates of 944, outermost first as it
+  // takes in the most hits
+  let loops = profile
+    .hot_loops()
+    .iter()
+    .map(|l| (l.start, l.end, l.iterations))
+    .collect::<Vec<_>>();
+  assert_eq!(loops, [(1, 26, 1), (2, 15, 944), (3, 11, 944 * 945)]);
+}
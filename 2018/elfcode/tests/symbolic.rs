@@ -0,0 +1,55 @@
+use elfcode::{explore, Cpu, Limits, Outcome};
+
+/** Paths through `source` with r0 unknown, halting soonest first, each as
+ * its outcome, steps and constraints */
+fn paths(source: &str, steps: u64) -> Vec<(Outcome, u64, String)> {
+  let mut cpu = Cpu::<u64, 6>::new();
+  cpu.load(source.parse().unwrap()).unwrap();
+  let limits = Limits {
+    steps,
+    ..Limits::default()
+  };
+  let mut paths = explore(&cpu, 0, limits);
+  paths.sort_by_key(|p| (p.outcome != Outcome::Halted, p.steps));
+  paths
+    .into_iter()
+    .map(|p| {
+      let conds = p.constraints.iter().map(|c| c.to_string());
+      (p.outcome, p.steps, conds.collect::<Vec<_>>().join(" and "))
+    })
+    .collect()
+}
+
+#[test]
+fn day_19() {
+  // line 25 jumps to r0 + 26: past the end for 10 or more, else into setup
+  // of a divisor sum too long to finish within the limit
+  let found = paths(include_str!("../../day_19/input/input"), 1000);
+  let limit = Outcome::Unknown("step limit reached".into());
+  let mut expected = vec![(Outcome::Halted, 10, "10 <= r0".to_string())];
+  expected
+    .extend((0..10).map(|v| (limit.clone(), 1000, format!("r0 == {}", v))));
+  assert_eq!(found, expected);
+}
+
+#[test]
+fn forks_on_comparisons() {
+  // halts if r0 is 7, traps writing r9 if it's over 20 and loops otherwise
+  let source = "#ip 5
+eqri 0 7 1
+addr 1 5 5
+seti 3 0 5
+seti 9 0 5
+gtri 0 20 1
+addr 1 5 5
+seti 3 0 5
+seti 0 0 9";
+  assert_eq!(
+    paths(source, 1000),
+    [
+      (Outcome::Halted, 3, "r0 == 7".to_string()),
+      (Outcome::Trapped, 6, "r0 != 7 and r0 > 20".to_string()),
+      (Outcome::Loops, 9, "r0 != 7 and r0 <= 20".to_string()),
+    ]
+  );
+}
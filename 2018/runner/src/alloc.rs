@@ -0,0 +1,48 @@
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  sync::atomic::{AtomicU64, Ordering::Relaxed},
+};
+
+static COUNT: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// System allocator counting allocations and bytes allocated; reallocations
+/// count as allocations of the new size.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    COUNT.fetch_add(1, Relaxed);
+    BYTES.fetch_add(layout.size() as u64, Relaxed);
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+
+  unsafe fn realloc(
+    &self,
+    ptr: *mut u8,
+    layout: Layout,
+    new_size: usize,
+  ) -> *mut u8 {
+    COUNT.fetch_add(1, Relaxed);
+    BYTES.fetch_add(new_size as u64, Relaxed);
+    System.realloc(ptr, layout, new_size)
+  }
+}
+
+/// Allocations made, and bytes allocated, so far
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Allocs {
+  pub count: u64,
+  pub bytes: u64,
+}
+
+pub fn allocs() -> Allocs {
+  Allocs {
+    count: COUNT.load(Relaxed),
+    bytes: BYTES.load(Relaxed),
+  }
+}
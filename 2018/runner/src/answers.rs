@@ -0,0 +1,38 @@
+use std::error::Error;
+
+/** Expected answers of parts 1 and 2 from the runner’s text output; a part
+ * without a `Part N:` line has none */
+pub fn parse(text: &str) -> Result<[Option<String>; 2], Box<dyn Error>> {
+  let mut answers = [None, None];
+  let mut current: Option<&mut String> = None;
+  for (idx, line) in text.lines().enumerate() {
+    let header = line
+      .strip_prefix("Part ")
+      .and_then(|rest| rest.split_once(':'));
+    match header {
+      Some((part @ ("1" | "2"), answer)) => {
+        let slot = &mut answers[part.parse::<usize>()? - 1];
+        if slot.is_some() {
+          return Err(
+            format!("Part {} repeats at line {}", part, idx + 1).into(),
+          );
+        }
+        current = Some(slot.insert(answer.trim().to_string()));
+      }
+      _ => match current.as_deref_mut() {
+        // answer continues on this line
+        Some(answer) => {
+          if !answer.is_empty() {
+            answer.push('\n');
+          }
+          answer.push_str(line);
+        }
+        None if line.trim().is_empty() => (),
+        None => {
+          return Err(format!("Expected `Part N:` at line {}", idx + 1).into())
+        }
+      },
+    }
+  }
+  Ok(answers)
+}
@@ -0,0 +1,223 @@
+use crate::{
+  alloc::{self, Allocs},
+  solvers::Solver,
+};
+use std::{
+  collections::BTreeMap,
+  error::Error,
+  fs,
+  path::Path,
+  time::{Duration, Instant},
+};
+
+// slower than the baseline by this factor and `NOISE` is a regression
+const SLOWDOWN: f64 = 1.2;
+const NOISE: Duration = Duration::from_micros(100);
+
+/// Step of a solver e.g. `2018 1 part 2`
+type Key = (u16, u8, String);
+
+/// Timings of a step over all runs, with allocations of the first
+struct Stats {
+  min: Duration,
+  median: Duration,
+  max: Duration,
+  allocs: Allocs,
+}
+
+/// Median time and allocations of a step recorded earlier
+struct Baseline {
+  median: Duration,
+  allocs: Allocs,
+}
+
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration, Allocs) {
+  let before = alloc::allocs();
+  let start = Instant::now();
+  let result = f();
+  let elapsed = start.elapsed();
+  let after = alloc::allocs();
+  let allocs = Allocs {
+    count: after.count - before.count,
+    bytes: after.bytes - before.bytes,
+  };
+  (result, elapsed, allocs)
+}
+
+fn stats(mut times: Vec<Duration>, allocs: Allocs) -> Stats {
+  times.sort_unstable();
+  Stats {
+    min: times[0],
+    median: times[times.len() / 2],
+    max: times[times.len() - 1],
+    allocs,
+  }
+}
+
+/** Runs parse and each part of `solver` `runs` times on its input; parts
+ * run on a fresh parse each time so nothing cached is reused across runs */
+fn bench(
+  solver: &Solver,
+  runs: usize,
+) -> Result<Vec<(String, Stats)>, Box<dyn Error>> {
+  let input = solver.read("input")?;
+  let steps = solver.parts as usize + 1;
+  let mut times = vec![Vec::with_capacity(runs); steps];
+  let mut allocs = vec![Allocs::default(); steps];
+  for run in 0..runs {
+    let (solution, elapsed, a) = measure(|| (solver.parse)(&input));
+    let solution = solution?;
+    times[0].push(elapsed);
+    if run == 0 {
+      allocs[0] = a;
+    }
+    for part in 1..=solver.parts {
+      let (_, elapsed, a) = measure(|| solution.part(part));
+      times[part as usize].push(elapsed);
+      if run == 0 {
+        allocs[part as usize] = a;
+      }
+    }
+  }
+  Ok(
+    times
+      .into_iter()
+      .zip(allocs)
+      .enumerate()
+      .map(|(step, (times, allocs))| {
+        let name = match step {
+          0 => "parse".to_string(),
+          part => format!("part {}", part),
+        };
+        (name, stats(times, allocs))
+      })
+      .collect(),
+  )
+}
+
+/** Baseline as tab-separated `year day step median_ns allocs bytes` lines;
+ * empty if the file doesn’t exist */
+fn load(path: &Path) -> Result<BTreeMap<Key, Baseline>, Box<dyn Error>> {
+  let text = match fs::read_to_string(path) {
+    Ok(text) => text,
+    Err(_) => return Ok(BTreeMap::new()),
+  };
+  text
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.starts_with('#') && !line.trim().is_empty())
+    .map(|(idx, line)| {
+      let invalid = || format!("Invalid baseline at line {}", idx + 1);
+      match line.split('\t').collect::<Vec<_>>()[..] {
+        [year, day, step, median, count, bytes] => Ok((
+          (
+            year.parse().map_err(|_| invalid())?,
+            day.parse().map_err(|_| invalid())?,
+            step.to_string(),
+          ),
+          Baseline {
+            median: Duration::from_nanos(
+              median.parse().map_err(|_| invalid())?,
+            ),
+            allocs: Allocs {
+              count: count.parse().map_err(|_| invalid())?,
+              bytes: bytes.parse().map_err(|_| invalid())?,
+            },
+          },
+        )),
+        _ => Err(invalid().into()),
+      }
+    })
+    .collect()
+}
+
+fn save(
+  path: &Path,
+  baseline: &BTreeMap<Key, Baseline>,
+) -> Result<(), Box<dyn Error>> {
+  let mut text = String::from("# year\tday\tstep\tmedian_ns\tallocs\tbytes\n");
+  for ((year, day, step), b) in baseline {
+    text += &format!(
+      "{}\t{}\t{}\t{}\t{}\t{}\n",
+      year,
+      day,
+      step,
+      b.median.as_nanos(),
+      b.allocs.count,
+      b.allocs.bytes
+    );
+  }
+  fs::write(path, text)?;
+  Ok(())
+}
+
+fn ms(d: Duration) -> String {
+  format!("{:.3} ms", d.as_secs_f64() * 1e3)
+}
+
+/** Versus `baseline`: change in median time, flagged when the step got
+ * slower or allocates more */
+fn compare(stats: &Stats, baseline: Option<&Baseline>) -> (String, bool) {
+  let b = match baseline {
+    Some(b) => b,
+    None => return (String::from("-"), false),
+  };
+  let change = stats.median.as_secs_f64() / b.median.as_secs_f64().max(1e-9);
+  let slower = change > SLOWDOWN && stats.median > b.median + NOISE;
+  let more_allocs = stats.allocs.count > b.allocs.count;
+  let mut note = format!("{:+.0}%", (change - 1.0) * 100.0);
+  if slower {
+    note += " SLOWER";
+  }
+  if more_allocs {
+    note += " MORE ALLOCS";
+  }
+  (note, slower || more_allocs)
+}
+
+/** Benchmarks `solvers`, printing a table with a comparison against the
+ * baseline at `path`; with `update` the baseline takes this run’s numbers.
+ * Returns steps that regressed. */
+pub fn run(
+  solvers: &[Solver],
+  runs: usize,
+  path: &Path,
+  update: bool,
+) -> Result<usize, Box<dyn Error>> {
+  let mut baseline = load(path)?;
+  let mut regressed = 0;
+  println!(
+    "{:<12} {:<7} {:>12} {:>12} {:>12} {:>10} {:>12}  vs baseline",
+    "Puzzle", "Step", "Min", "Median", "Max", "Allocs", "Bytes"
+  );
+  for solver in solvers {
+    for (step, stats) in bench(solver, runs)? {
+      let key = (solver.year, solver.day, step);
+      let (note, flagged) = compare(&stats, baseline.get(&key));
+      regressed += flagged as usize;
+      println!(
+        "{:<12} {:<7} {:>12} {:>12} {:>12} {:>10} {:>12}  {}",
+        format!("{} day {:02}", solver.year, solver.day),
+        key.2,
+        ms(stats.min),
+        ms(stats.median),
+        ms(stats.max),
+        stats.allocs.count,
+        stats.allocs.bytes,
+        note
+      );
+      if update {
+        let b = Baseline {
+          median: stats.median,
+          allocs: stats.allocs,
+        };
+        baseline.insert(key, b);
+      }
+    }
+  }
+  if update {
+    save(path, &baseline)?;
+    println!("Baseline saved to {}", path.display());
+  }
+  Ok(regressed)
+}
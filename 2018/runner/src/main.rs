@@ -0,0 +1,203 @@
+mod alloc;
+mod answers;
+mod bench;
+mod solvers;
+
+use aoc::{Format, Output};
+use std::{
+  env,
+  error::Error,
+  fs,
+  panic::{self, AssertUnwindSafe},
+  path::{Path, PathBuf},
+  process,
+};
+
+// counts allocations for benchmarks
+#[global_allocator]
+static ALLOC: alloc::Counting = alloc::Counting;
+
+const USAGE: &str = "Usage: aoc run YEAR DAY [--input PATH|-] [--part 1|2]
+                   [--format text|json]
+       aoc test [YEAR [DAY]]
+       aoc bench [YEAR [DAY]] [--runs N] [--baseline PATH] [--save]
+       aoc list";
+
+/** Value following `flag` on the command line */
+fn option(args: &[String], flag: &str) -> Option<String> {
+  args.iter().skip_while(|arg| *arg != flag).nth(1).cloned()
+}
+
+/** Year and day given as the first two arguments that aren’t options */
+fn year_day(
+  args: &[String],
+) -> Result<(Option<u16>, Option<u8>), Box<dyn Error>> {
+  let mut positional = Vec::new();
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      // skip the option’s value too
+      "--input" | "--part" | "--format" | "--runs" | "--baseline" => {
+        args.next();
+      }
+      _ if arg.starts_with("--") => (),
+      _ => positional.push(arg),
+    }
+  }
+  let year = positional.first().map(|y| y.parse()).transpose()?;
+  let day = positional.get(1).map(|d| d.parse()).transpose()?;
+  Ok((year, day))
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+  let (year, day) = match args {
+    [year, day, ..] => (year.parse::<u16>()?, day.parse::<u8>()?),
+    _ => return Err(USAGE.into()),
+  };
+  let solver = solvers::find(year, day)
+    .ok_or_else(|| format!("No solver for {} day {}", year, day))?;
+  let input = match option(args, "--input").as_deref() {
+    Some("-") => aoc::read_stdin()?,
+    Some(path) => fs::read_to_string(path)?,
+    None => solver.read("input")?,
+  };
+  let parts = match option(args, "--part").as_deref() {
+    None => (1..=solver.parts).collect::<Vec<_>>(),
+    Some(part) => match part.parse() {
+      Ok(p) if (1..=solver.parts).contains(&p) => vec![p],
+      _ => {
+        return Err(format!("No part {} on {} day {}", part, year, day).into())
+      }
+    },
+  };
+
+  let format = match option(args, "--format") {
+    Some(format) => format.parse()?,
+    None => Format::default(),
+  };
+
+  // `Part N` labels make text output what `input/answers` holds
+  let out = Output::new(year, day, format);
+  let solution = (solver.parse)(&input)?;
+  for part in parts {
+    out.part(part, &format!("Part {}", part), || solution.part(part));
+  }
+  Ok(())
+}
+
+/// Regression tallies
+#[derive(Default)]
+struct Tally {
+  pass: usize,
+  fail: usize,
+  missing: usize,
+}
+
+/** Runs solvers of `year` (all if not given), or just `day`, on their inputs
+ * and checks answers against each day’s `input/answers` */
+fn test(args: &[String]) -> Result<Tally, Box<dyn Error>> {
+  let (year, day) = year_day(args)?;
+  let mut tally = Tally::default();
+  for solver in solvers::select(year, day) {
+    let label = format!("{} day {:02}", solver.year, solver.day);
+    let expected = match solver.read("answers") {
+      Ok(text) => answers::parse(&text)
+        .map_err(|e| format!("{} answers: {}", label, e))?,
+      Err(_) => [None, None],
+    };
+    // a panicking or failing solver fails its parts rather than the run
+    let solved = solver.read("input").and_then(|input| {
+      panic::catch_unwind(|| (solver.parse)(&input))
+        .unwrap_or_else(|_| Err("parse panicked".into()))
+    });
+    for part in 1..=solver.parts {
+      let expected = &expected[part as usize - 1];
+      let found = match &solved {
+        Ok(solution) => {
+          panic::catch_unwind(AssertUnwindSafe(|| solution.part(part)))
+            .map(|answer| answer.to_string())
+            .map_err(|_| "panicked".to_string())
+        }
+        Err(e) => Err(e.to_string()),
+      };
+      match (expected, found) {
+        (None, _) => {
+          tally.missing += 1;
+          println!("{} part {}: missing", label, part);
+        }
+        (Some(expected), Ok(found)) if *expected == found => {
+          tally.pass += 1;
+          println!("{} part {}: pass", label, part);
+        }
+        (Some(expected), found) => {
+          tally.fail += 1;
+          println!("{} part {}: FAIL", label, part);
+          println!("  expected: {}", expected);
+          match found {
+            Ok(found) => println!("  found:    {}", found),
+            Err(e) => println!("  error:    {}", e),
+          }
+        }
+      }
+    }
+  }
+  println!(
+    "{} passed, {} failed, {} missing",
+    tally.pass, tally.fail, tally.missing
+  );
+  Ok(tally)
+}
+
+/** Benchmarks solvers of `year` (all if not given), or just `day`; compares
+ * against and, with `--save`, updates the baseline (by default
+ * `bench-baseline.tsv` in the workspace) */
+fn bench(args: &[String]) -> Result<(), Box<dyn Error>> {
+  let (year, day) = year_day(args)?;
+  let runs = match option(args, "--runs") {
+    Some(n) => n.parse::<usize>().ok().filter(|&n| n > 0).ok_or_else(|| {
+      format!("Invalid value for --runs: {}; expected 1 or more", n)
+    })?,
+    None => 5,
+  };
+  let path = match option(args, "--baseline") {
+    Some(path) => PathBuf::from(path),
+    None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../bench-baseline.tsv"),
+  };
+  let save = args.iter().any(|arg| arg == "--save");
+  match bench::run(&solvers::select(year, day), runs, &path, save)? {
+    0 => Ok(()),
+    n => Err(format!("{} step(s) regressed against the baseline", n).into()),
+  }
+}
+
+fn list() {
+  for s in solvers::all() {
+    let input = match s.file("input").exists() {
+      true => "",
+      false => "  (no input)",
+    };
+    println!("{} day {:02}: {} part(s){}", s.year, s.day, s.parts, input);
+  }
+}
+
+fn main() {
+  let args = env::args().skip(1).collect::<Vec<_>>();
+  let result = match args.first().map(String::as_str) {
+    Some("run") => run(&args[1..]),
+    Some("test") => test(&args[1..]).and_then(|tally| match tally.fail {
+      0 => Ok(()),
+      n => Err(format!("{} answer(s) regressed", n).into()),
+    }),
+    Some("bench") => bench(&args[1..]),
+    Some("list") => {
+      list();
+      Ok(())
+    }
+    _ => Err(USAGE.into()),
+  };
+  // print as is; usage spans lines
+  if let Err(e) = result {
+    eprintln!("{}", e);
+    process::exit(1);
+  }
+}
@@ -0,0 +1,112 @@
+use aoc::Answer;
+use std::{
+  error::Error,
+  fs,
+  path::{Path, PathBuf},
+};
+
+/// Parsed puzzle input, ready to solve either part
+pub trait Solution {
+  fn part(&self, part: u8) -> Answer;
+}
+
+struct Parsed<I> {
+  input: I,
+  parts: [fn(&I) -> Answer; 2],
+}
+
+impl<I> Solution for Parsed<I> {
+  fn part(&self, part: u8) -> Answer {
+    (self.parts[part as usize - 1])(&self.input)
+  }
+}
+
+pub type Parse = fn(&str) -> Result<Box<dyn Solution>, Box<dyn Error>>;
+
+/// Solver of a day’s puzzle
+pub struct Solver {
+  pub year: u16,
+  pub day: u8,
+  /// Parts with a puzzle; day 25 has just one
+  pub parts: u8,
+  pub parse: Parse,
+}
+
+impl Solver {
+  /** File `name` in the day’s `input/`, which sits next to the runner’s
+   * package; `input` is its default input and `answers` its expected answers
+   */
+  pub fn file(&self, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+      .join("..")
+      .join(format!("day_{:02}", self.day))
+      .join("input")
+      .join(name)
+  }
+
+  pub fn read(&self, name: &str) -> Result<String, Box<dyn Error>> {
+    let path = self.file(name);
+    fs::read_to_string(&path)
+      .map_err(|e| format!("{}: {}", path.display(), e).into())
+  }
+}
+
+macro_rules! day {
+  ($day:literal, $krate:ident, $parts:literal) => {
+    Solver {
+      year: 2018,
+      day: $day,
+      parts: $parts,
+      parse: |input| {
+        Ok(Box::new(Parsed {
+          input: $krate::parse(input)?,
+          parts: [$krate::part1, $krate::part2],
+        }))
+      },
+    }
+  };
+}
+
+/** Solvers implemented, by year and day */
+pub fn all() -> Vec<Solver> {
+  vec![
+    day!(1, day_01, 2),
+    day!(2, day_02, 2),
+    day!(3, day_03, 2),
+    day!(4, day_04, 2),
+    day!(5, day_05, 2),
+    day!(6, day_06, 2),
+    day!(7, day_07, 2),
+    day!(8, day_08, 2),
+    day!(9, day_09, 2),
+    day!(10, day_10, 2),
+    day!(11, day_11, 2),
+    day!(12, day_12, 2),
+    day!(13, day_13, 2),
+    day!(14, day_14, 2),
+    day!(15, day_15, 2),
+    day!(16, day_16, 2),
+    day!(17, day_17, 2),
+    day!(18, day_18, 2),
+    day!(19, day_19, 2),
+    day!(20, day_20, 2),
+    day!(21, day_21, 2),
+    day!(22, day_22, 2),
+    day!(23, day_23, 2),
+    day!(24, day_24, 2),
+    day!(25, day_25, 1),
+  ]
+}
+
+pub fn find(year: u16, day: u8) -> Option<Solver> {
+  all().into_iter().find(|s| s.year == year && s.day == day)
+}
+
+/** Solvers of `year` (all if not given), or just of `day` */
+pub fn select(year: Option<u16>, day: Option<u8>) -> Vec<Solver> {
+  all()
+    .into_iter()
+    .filter(|s| year.is_none_or(|y| s.year == y))
+    .filter(|s| day.is_none_or(|d| s.day == d))
+    .collect()
+}